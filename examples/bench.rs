@@ -0,0 +1,85 @@
+//! Rough timings of the hot canvas operations, for comparing changes to the board storage.
+//!
+//! Run with `cargo run --release --example bench`.
+
+use std::collections::HashSet;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use jcblocks::prelude::*;
+
+const ITERATIONS: u32 = 100_000;
+
+/// Run `f` `ITERATIONS` times and print the average time per call.
+fn time(name: &str, mut f: impl FnMut()) {
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        f();
+    }
+    let per_call = start.elapsed() / ITERATIONS;
+    println!(
+        "{name:<32} {:>10.1?}",
+        per_call.max(Duration::from_nanos(1))
+    );
+}
+
+/// A half full board with no completed lines.
+fn cluttered(size: usize) -> Canvas {
+    let mut board = Canvas::square(size);
+    for row in 0..size as i32 {
+        for column in 0..size as i32 {
+            if (row * 7 + column * 3) % 5 < 2 || column == row {
+                board.set(row, column, PointStatus::Occupied((column % 6) as u8 + 1));
+            }
+        }
+    }
+    board
+}
+
+fn main() {
+    for size in [8, 16, 32] {
+        let board = cluttered(size);
+        let cells = size * size;
+        // The per cell storage is a byte for the status plus a bit in each of the two bitsets.
+        let bytes = cells + 2 * cells.div_ceil(64) * 8;
+        println!("{size}x{size} board, {bytes} bytes of cell storage");
+
+        time("clone", || {
+            black_box(board.clone());
+        });
+        time("contents", || {
+            black_box(board.contents());
+        });
+        time("count_holes", || {
+            black_box(board.count_holes());
+        });
+
+        let square = Block::rectangle(2, 2);
+        time("place and clear", || {
+            let mut scratch = board.clone();
+            if let Some(placement) = scratch.can_fit(&square) {
+                scratch.add(&placement);
+            }
+            black_box(scratch.clear_completed_lines());
+        });
+
+        // The same search as `can_fit`, one cell at a time.
+        let tee = Block::tee();
+        time("can_fit", || {
+            black_box(board.can_fit(&tee));
+        });
+        time("can_fit_at every origin", || {
+            let origin = (0..size as i32)
+                .flat_map(|column| (0..size as i32).map(move |row| (row, column)))
+                .find(|&(row, column)| board.can_fit_at(&tee, row, column));
+            black_box(origin);
+        });
+
+        let mut seen = HashSet::new();
+        time("hash", || {
+            black_box(seen.insert(board.clone()));
+        });
+
+        println!();
+    }
+}
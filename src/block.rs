@@ -1,15 +1,17 @@
-use std::collections::HashMap;
 use std::fmt::{Debug, Display, Formatter};
+use std::hash::{Hash, Hasher};
+use std::ops::{Add, AddAssign, Sub, SubAssign};
 
 use rand::distr::{Distribution, StandardUniform};
 use rand::{Rng, random};
 
 /// The smallest component of a peice.
-/// ```
+/// ```text
 /// ┌─┐
 /// └─┘
 /// ```
-#[derive(PartialEq, Debug, Clone)]
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Point {
     pub x: i32,
     pub y: i32,
@@ -31,23 +33,84 @@ impl Point {
         self.y = tmp;
         self
     }
+
+    /// Rotate right 90 degrees about `center`.
+    pub fn rotate_right_about(&mut self, center: &Point) -> &mut Self {
+        *self -= *center;
+        self.rotate_right();
+        *self += *center;
+        self
+    }
+
+    /// Rotate left 90 degrees about `center`.
+    pub fn rotate_left_about(&mut self, center: &Point) -> &mut Self {
+        *self -= *center;
+        self.rotate_left();
+        *self += *center;
+        self
+    }
+
+    /// Reflect across the y axis.
+    pub fn mirror_x(&mut self) -> &mut Self {
+        self.x = 0 - self.x;
+        self
+    }
+
+    /// Reflect across the x axis.
+    pub fn mirror_y(&mut self) -> &mut Self {
+        self.y = 0 - self.y;
+        self
+    }
+}
+
+impl Add for Point {
+    type Output = Point;
+
+    fn add(self, other: Point) -> Point {
+        Point {
+            x: self.x + other.x,
+            y: self.y + other.y,
+        }
+    }
+}
+
+impl Sub for Point {
+    type Output = Point;
+
+    fn sub(self, other: Point) -> Point {
+        Point {
+            x: self.x - other.x,
+            y: self.y - other.y,
+        }
+    }
 }
 
-impl Default for Point {
-    fn default() -> Self {
-        Self { x: 0, y: 0 }
+impl AddAssign for Point {
+    fn add_assign(&mut self, other: Point) {
+        *self = *self + other;
+    }
+}
+
+impl SubAssign for Point {
+    fn sub_assign(&mut self, other: Point) {
+        *self = *self - other;
     }
 }
 
 pub const MAX_RECTANGLE_EDGE: usize = 3;
 pub const MAX_LINE_LENGTH: usize = 5;
+pub const MAX_DIAGONAL_LENGTH: usize = 5;
 pub const MIN_ELLE_EDGE: usize = 2;
 pub const MAX_ELLE_EDGE: usize = 3;
+/// Largest distance from the origin, along either axis, of a point in a block built from
+/// arbitrary coordinates.
+pub const MAX_COORDINATE: i32 = 1024;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Variant {
     /// The following shapes can be created as a Rectangle:
-    /// ```
+    /// ```text
     /// ┌─┐ ┌─┬─┐ ┌─┬─┐ ┌─┬─┬─┐ ┌─┬─┬─┐
     /// └─┘ ├─┼─┤ ├─┼─┤ ├─┼─┼─┤ ├─┼─┼─┤
     ///     └─┴─┘ ├─┼─┤ └─┴─┴─┘ ├─┼─┼─┤
@@ -58,34 +121,174 @@ pub enum Variant {
     /// NOTE: Though you are free to create `Line`s this way, prefer using `Line` explicitly.
     Rectangle,
 
-    /// ```
+    /// ```text
     ///   ┌─┐  
     /// ┌─┼─┼─┐
     /// └─┴─┴─┘
     /// ```
     /// Where the origin (0,0) is the lower leftmost block.
     Tee,
+
+    /// Between 2 and `MAX_DIAGONAL_LENGTH` cells touching corner to corner, e.g.
+    /// ```text
+    ///     ┌─┐
+    ///   ┌─┼─┘
+    /// ┌─┼─┘
+    /// └─┘
+    /// ```
+    /// Where the origin (0,0) is the lower leftmost block.
     Diagonal,
     Elle,
 
+    /// ```text
     /// ┌─┬─┐    ┌─┬─┬─┐   ┌─┬─┬─┬─┐  ┌─┬─┬─┬─┬─┐
     /// └─┴─┘    └─┴─┴─┘   └─┴─┴─┴─┘  └─┴─┴─┴─┴─┘
+    /// ```
     /// Where the origin (0,0) is the lower leftmost block.
     Line,
+
+    /// ```text
+    ///   ┌─┬─┐
+    /// ┌─┼─┼─┘
+    /// └─┴─┘
+    /// ```
+    /// Where the origin (0,0) is the lower leftmost block.
+    Ess,
+
+    /// ```text
+    /// ┌─┬─┐
+    /// └─┼─┼─┐
+    ///   └─┴─┘
+    /// ```
+    /// Where the origin (0,0) is the lower leftmost block, so the top row starts at (-1,1).
+    Zed,
+
+    /// ```text
+    ///   ┌─┐
+    /// ┌─┼─┼─┐
+    /// └─┼─┼─┘
+    ///   └─┘
+    /// ```
+    /// Where the origin (0,0) is the lower left corner of the bounding box.
+    Plus,
+
+    /// One of the twelve five-cell shapes, see [`Pentomino`].
+    #[cfg(feature = "pentominoes")]
+    Pentomino(Pentomino),
+
+    /// Any other shape, built with [`Block::from_coords`].
+    Custom,
+}
+
+/// The twelve free pentominoes, named after the letters they resemble.
+#[cfg(feature = "pentominoes")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Pentomino {
+    F,
+    I,
+    L,
+    N,
+    P,
+    T,
+    U,
+    V,
+    W,
+    X,
+    Y,
+    Z,
+}
+
+#[cfg(feature = "pentominoes")]
+impl Pentomino {
+    pub const ALL: [Pentomino; 12] = [
+        Pentomino::F,
+        Pentomino::I,
+        Pentomino::L,
+        Pentomino::N,
+        Pentomino::P,
+        Pentomino::T,
+        Pentomino::U,
+        Pentomino::V,
+        Pentomino::W,
+        Pentomino::X,
+        Pentomino::Y,
+        Pentomino::Z,
+    ];
+
+    /// Cells of the shape as `(x, y)` pairs, with the origin at the lower left.
+    fn cells(&self) -> [(i32, i32); 5] {
+        match self {
+            Pentomino::F => [(1, 0), (0, 1), (1, 1), (1, 2), (2, 2)],
+            Pentomino::I => [(0, 0), (1, 0), (2, 0), (3, 0), (4, 0)],
+            Pentomino::L => [(0, 0), (1, 0), (2, 0), (3, 0), (0, 1)],
+            Pentomino::N => [(0, 0), (1, 0), (1, 1), (2, 1), (3, 1)],
+            Pentomino::P => [(0, 0), (1, 0), (0, 1), (1, 1), (0, 2)],
+            Pentomino::T => [(1, 0), (1, 1), (0, 2), (1, 2), (2, 2)],
+            Pentomino::U => [(0, 0), (1, 0), (2, 0), (0, 1), (2, 1)],
+            Pentomino::V => [(0, 0), (1, 0), (2, 0), (0, 1), (0, 2)],
+            Pentomino::W => [(0, 0), (1, 0), (1, 1), (2, 1), (2, 2)],
+            Pentomino::X => [(1, 0), (0, 1), (1, 1), (2, 1), (1, 2)],
+            Pentomino::Y => [(0, 0), (1, 0), (2, 0), (3, 0), (1, 1)],
+            Pentomino::Z => [(1, 0), (2, 0), (1, 1), (0, 2), (1, 2)],
+        }
+    }
+}
+
+#[cfg(feature = "pentominoes")]
+impl Distribution<Pentomino> for StandardUniform {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Pentomino {
+        Pentomino::ALL[rng.random_range(0..Pentomino::ALL.len())]
+    }
+}
+
+#[cfg(feature = "pentominoes")]
+impl Display for Pentomino {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        Debug::fmt(self, f)
+    }
 }
 
 impl Distribution<Variant> for StandardUniform {
     fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Variant {
-        match rng.random_range(0..=4) {
+        #[cfg(feature = "pentominoes")]
+        if rng.random_range(0..=5) == 5 {
+            return Variant::Pentomino(rng.random());
+        }
+
+        match rng.random_range(0..=7) {
             0 => Variant::Rectangle,
             1 => Variant::Tee,
             2 => Variant::Diagonal,
             3 => Variant::Elle,
+            4 => Variant::Ess,
+            5 => Variant::Zed,
+            6 => Variant::Plus,
             _ => Variant::Line,
         }
     }
 }
 
+impl Variant {
+    /// Palette index a block of this variant is drawn with unless told otherwise. Zero is left
+    /// free for empty cells.
+    pub fn default_color(&self) -> u8 {
+        match self {
+            Variant::Rectangle => 1,
+            Variant::Tee => 2,
+            Variant::Diagonal => 3,
+            Variant::Elle => 4,
+            Variant::Line => 5,
+            Variant::Ess => 6,
+            Variant::Zed => 7,
+            Variant::Plus => 8,
+            #[cfg(feature = "pentominoes")]
+            Variant::Pentomino(_) => 9,
+            Variant::Custom => 10,
+        }
+    }
+}
+
 impl Display for Variant {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         let name = match self {
@@ -94,23 +297,139 @@ impl Display for Variant {
             Variant::Tee => "Tee",
             Variant::Rectangle => "Rectangle",
             Variant::Line => "Line",
+            Variant::Ess => "Ess",
+            Variant::Zed => "Zed",
+            Variant::Plus => "Plus",
+            #[cfg(feature = "pentominoes")]
+            Variant::Pentomino(shape) => return write!(f, "{shape}-Pentomino"),
+            Variant::Custom => "Custom",
         };
         write!(f, "{name}")
     }
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Dimension {
     pub height: usize,
     pub width: usize,
 }
 
+/// How many of a block's four rotations are distinct from one another.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Symmetry {
+    /// Every rotation looks the same, e.g. a square.
+    Full,
+    /// Half turns look the same, e.g. a line.
+    TwoFold,
+    /// All four rotations are distinct, e.g. a tee.
+    None,
+}
+
+impl Symmetry {
+    /// Number of distinct rotations a block with this symmetry has.
+    pub fn distinct_rotations(&self) -> usize {
+        match self {
+            Symmetry::Full => 1,
+            Symmetry::TwoFold => 2,
+            Symmetry::None => 4,
+        }
+    }
+}
+
+/// Reasons a set of coordinates cannot make a block.
+#[derive(Debug, Clone, PartialEq)]
+pub enum BlockError {
+    /// No coordinates were given.
+    Empty,
+    /// The same cell was listed more than once.
+    DuplicatePoint(Point),
+    /// A point lies further than [`MAX_COORDINATE`] from the origin.
+    OutOfRange(Point),
+}
+
+impl Display for BlockError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BlockError::Empty => write!(f, "a block needs at least one point"),
+            BlockError::DuplicatePoint(p) => {
+                write!(f, "point ({}, {}) appears more than once", p.x, p.y)
+            }
+            BlockError::OutOfRange(p) => {
+                write!(f, "point ({}, {}) is too far from the origin", p.x, p.y)
+            }
+        }
+    }
+}
+
+impl std::error::Error for BlockError {}
+
 #[derive(Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "BlockData")
+)]
 pub struct Block {
     coords: Vec<Point>,
     variant: Variant,
+    color: u8,
+}
+
+/// The serialized form of a [`Block`], checked like [`Block::from_coords`] before it becomes one
+/// so that an untrusted shape is still safe to place.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct BlockData {
+    coords: Vec<Point>,
+    variant: Variant,
+    /// Missing from blocks saved before colors existed, which get their variant's default.
+    color: Option<u8>,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<BlockData> for Block {
+    type Error = BlockError;
+
+    fn try_from(data: BlockData) -> Result<Self, Self::Error> {
+        check_coords(&data.coords)?;
+        let color = data.color.unwrap_or_else(|| data.variant.default_color());
+        Ok(Block::from_parts(data.coords, data.variant, color))
+    }
+}
+
+/// Fails if `coords` is empty, lists the same cell twice or strays past [`MAX_COORDINATE`].
+fn check_coords(coords: &[Point]) -> Result<(), BlockError> {
+    if coords.is_empty() {
+        return Err(BlockError::Empty);
+    }
+
+    for (i, p) in coords.iter().enumerate() {
+        if p.x.abs() > MAX_COORDINATE || p.y.abs() > MAX_COORDINATE {
+            return Err(BlockError::OutOfRange(*p));
+        }
+        if coords[..i].contains(p) {
+            return Err(BlockError::DuplicatePoint(*p));
+        }
+    }
+
+    Ok(())
 }
 
 impl Block {
+    fn new(coords: Vec<Point>, variant: Variant) -> Self {
+        let color = variant.default_color();
+        Self {
+            coords,
+            variant,
+            color,
+        }
+    }
+
+    /// Rebuild a block from its parts, e.g. when replaying a recorded move.
+    pub(crate) fn from_parts(coords: Vec<Point>, variant: Variant, color: u8) -> Self {
+        Self::new(coords, variant).with_color(color)
+    }
+
     /// Tee constructor. Tees are always the same size.
     pub fn tee() -> Self {
         let mut coords = Vec::new();
@@ -120,10 +439,7 @@ impl Block {
         }
         coords.push(Point { x: 1, y: 1 });
 
-        Self {
-            coords,
-            variant: Variant::Tee,
-        }
+        Self::new(coords, Variant::Tee)
     }
 
     /// Width/Height are restricted to the range [1, `MAX_RECTANGLE_EDGE`].
@@ -139,10 +455,7 @@ impl Block {
             }
         }
 
-        Self {
-            coords,
-            variant: Variant::Rectangle,
-        }
+        Self::new(coords, Variant::Rectangle)
     }
 
     /// Length is restricted to the range [2, `MAX_LINE_LENGTH`].
@@ -150,32 +463,42 @@ impl Block {
         let mut coords = Vec::new();
 
         for i in 0..length.clamp(2, MAX_LINE_LENGTH) {
-            coords.push(Point {
-                x: i as i32,
-                y: 0 as i32,
-            });
+            coords.push(Point { x: i as i32, y: 0 });
         }
 
-        Self {
-            coords,
-            variant: Variant::Line,
+        Self::new(coords, Variant::Line)
+    }
+
+    /// Same as [`Block::line`], spelled out for symmetry with [`Block::line_vertical`].
+    pub fn line_horizontal(length: usize) -> Self {
+        Self::line(length)
+    }
+
+    /// A line standing upright, without needing a rotation.
+    ///
+    /// Length is restricted to the range [2, `MAX_LINE_LENGTH`].
+    pub fn line_vertical(length: usize) -> Self {
+        let mut coords = Vec::new();
+
+        for i in 0..length.clamp(2, MAX_LINE_LENGTH) {
+            coords.push(Point { x: 0, y: i as i32 });
         }
+
+        Self::new(coords, Variant::Line)
     }
 
+    /// Width is restricted to the range [2, `MAX_DIAGONAL_LENGTH`].
     pub fn diagonal(width: usize) -> Self {
         let mut coords = Vec::new();
 
-        for i in 0..width {
+        for i in 0..width.clamp(2, MAX_DIAGONAL_LENGTH) {
             coords.push(Point {
                 x: i as i32,
                 y: i as i32,
             });
         }
 
-        Self {
-            coords,
-            variant: Variant::Diagonal,
-        }
+        Self::new(coords, Variant::Diagonal)
     }
 
     /// Width/Height are restricted to the range [`MIN_ELLE_EDGE`, `MAX_ELLE_EDGE`].
@@ -191,10 +514,181 @@ impl Block {
             coords.push(Point { x: 0, y: i as i32 });
         }
 
-        Self {
-            coords,
-            variant: Variant::Elle,
+        Self::new(coords, Variant::Elle)
+    }
+
+    /// Ess constructor. Esses are always the same size.
+    pub fn ess() -> Self {
+        Self::new(
+            vec![
+                Point { x: 0, y: 0 },
+                Point { x: 1, y: 0 },
+                Point { x: 1, y: 1 },
+                Point { x: 2, y: 1 },
+            ],
+            Variant::Ess,
+        )
+    }
+
+    /// Zed constructor. Zeds are always the same size.
+    pub fn zed() -> Self {
+        Self::new(
+            vec![
+                Point { x: 0, y: 0 },
+                Point { x: 1, y: 0 },
+                Point { x: -1, y: 1 },
+                Point { x: 0, y: 1 },
+            ],
+            Variant::Zed,
+        )
+    }
+
+    /// Plus constructor. Pluses are always the same size.
+    pub fn plus() -> Self {
+        Self::new(
+            vec![
+                Point { x: 1, y: 0 },
+                Point { x: 0, y: 1 },
+                Point { x: 1, y: 1 },
+                Point { x: 2, y: 1 },
+                Point { x: 1, y: 2 },
+            ],
+            Variant::Plus,
+        )
+    }
+
+    /// Build a custom shape from arbitrary coordinates, shifted into the first quadrant.
+    ///
+    /// Fails if `coords` is empty, lists the same cell twice or has a point further than
+    /// [`MAX_COORDINATE`] from the origin.
+    pub fn from_coords(coords: Vec<Point>) -> Result<Self, BlockError> {
+        check_coords(&coords)?;
+
+        let mut block = Self::new(coords, Variant::Custom);
+        block.normalize();
+        Ok(block)
+    }
+
+    /// Pentomino constructor. Pentominoes are always the same size.
+    #[cfg(feature = "pentominoes")]
+    pub fn pentomino(shape: Pentomino) -> Self {
+        let coords = shape
+            .cells()
+            .into_iter()
+            .map(|(x, y)| Point { x, y })
+            .collect();
+
+        Self::new(coords, Variant::Pentomino(shape))
+    }
+
+    /// Number of cells the block occupies.
+    pub fn cell_count(&self) -> usize {
+        self.coords.len()
+    }
+
+    /// Number of cells the block occupies; the same as [`Block::cell_count`].
+    pub fn area(&self) -> usize {
+        self.cell_count()
+    }
+
+    /// Every block used for generation, each in its default orientation.
+    pub fn standard_set() -> Vec<Block> {
+        #[allow(unused_mut)]
+        let mut blocks = vec![
+            Block::rectangle(3, 3),
+            Block::rectangle(3, 2),
+            Block::rectangle(2, 3),
+            Block::rectangle(2, 2),
+            Block::rectangle(1, 1),
+            Block::tee(),
+            Block::line(2),
+            Block::line(3),
+            Block::line(4),
+            Block::line(5),
+            Block::elle(3, 3),
+            Block::elle(3, 2),
+            Block::elle(2, 3),
+            Block::elle(2, 2),
+            Block::diagonal(2),
+            Block::diagonal(3),
+            Block::diagonal(4),
+            Block::plus(),
+            Block::ess(),
+            Block::zed(),
+        ];
+
+        #[cfg(feature = "pentominoes")]
+        blocks.extend(Pentomino::ALL.into_iter().map(Block::pentomino));
+
+        blocks
+    }
+
+    /// Relative likelihood of drawing `self` from [`Block::standard_set`]. The pentominoes share
+    /// the weight of a single block between them, so they do not crowd out the other shapes.
+    pub fn standard_set_weight(&self) -> f32 {
+        #[cfg(feature = "pentominoes")]
+        if let Variant::Pentomino(_) = self.variant {
+            return 1.0 / Pentomino::ALL.len() as f32;
         }
+
+        1.0
+    }
+
+    /// Every block in [`Block::standard_set`] in each of its distinct rotations.
+    ///
+    /// Orientations are only listed once, even where two blocks in the set are rotations of one
+    /// another.
+    pub fn catalog_all_orientations() -> Vec<Block> {
+        let mut catalog: Vec<Block> = Vec::new();
+        let mut seen = Vec::new();
+
+        for mut block in Block::standard_set() {
+            for _ in 0..block.symmetry().distinct_rotations() {
+                let normalized = block.normalized_coordinates();
+                if !seen.contains(&normalized) {
+                    seen.push(normalized);
+                    catalog.push(block.clone());
+                }
+                block.rotate_left();
+            }
+        }
+
+        catalog
+    }
+
+    /// How hard the block is to place, for rewarding players who use awkward blocks. Larger
+    /// blocks weigh more, and irregular shapes weigh double.
+    pub fn placement_weight(&self) -> usize {
+        let awkwardness = match self.variant {
+            Variant::Rectangle | Variant::Line => 1,
+            Variant::Tee
+            | Variant::Elle
+            | Variant::Diagonal
+            | Variant::Ess
+            | Variant::Zed
+            | Variant::Plus
+            | Variant::Custom => 2,
+            #[cfg(feature = "pentominoes")]
+            Variant::Pentomino(_) => 2,
+        };
+
+        self.cell_count() * awkwardness
+    }
+
+    /// The kind of shape this block was built as.
+    pub fn variant(&self) -> &Variant {
+        &self.variant
+    }
+
+    /// Palette index used to draw the block.
+    pub fn color(&self) -> u8 {
+        self.color
+    }
+
+    /// Replace the block's default color.
+    pub fn with_color(mut self, color: u8) -> Self {
+        self.color = color;
+        self
     }
 
     pub fn coordinates(&self) -> &Vec<Point> {
@@ -205,54 +699,66 @@ impl Block {
         &mut self.coords
     }
 
-    pub fn dimensions(&self) -> Dimension {
-        // diagonals can be computed trivially
-        if let Variant::Diagonal = self.variant {
-            let points = self.coords.len();
-            return Dimension {
-                width: points,
-                height: points,
-            };
+    /// Returns true if the block, as currently oriented, occupies the cell at `x`/`y`.
+    pub fn contains(&self, x: i32, y: i32) -> bool {
+        self.coords.contains(&Point { x, y })
+    }
+
+    /// Returns true if `p` lies within the block's bounding box, whether or not the block
+    /// actually occupies that cell.
+    pub fn bounding_contains(&self, p: &Point) -> bool {
+        if self.coords.is_empty() {
+            return false;
         }
 
-        // Create a histogram of x/y values then just grab the max.
-        let height: i32 = self
-            .coordinates()
-            .iter()
-            .fold(&mut HashMap::new(), |acc, coord| {
-                if let Some(val) = acc.get(&coord.x) {
-                    acc.insert(coord.x, val + 1);
-                } else {
-                    acc.insert(coord.x, 1);
-                };
+        let (min, max) = self.bounding_box();
+        (min.x..=max.x).contains(&p.x) && (min.y..=max.y).contains(&p.y)
+    }
 
-                acc
-            })
-            .drain()
-            .map(|(_k, v)| v)
-            .max()
-            .unwrap_or(0);
+    /// The lower left and upper right corners of the smallest box around the block, which need
+    /// not be points of the block itself. An empty block is boxed at the origin.
+    pub fn bounding_box(&self) -> (Point, Point) {
+        let xs = self.coords.iter().map(|p| p.x);
+        let ys = self.coords.iter().map(|p| p.y);
+
+        (
+            Point {
+                x: xs.clone().min().unwrap_or(0),
+                y: ys.clone().min().unwrap_or(0),
+            },
+            Point {
+                x: xs.max().unwrap_or(0),
+                y: ys.max().unwrap_or(0),
+            },
+        )
+    }
 
-        let width: i32 = self
-            .coordinates()
+    /// Coordinates translated so the block's origin sits at `row`/`column` of a board. Points
+    /// pushed past the range of `i32` stop at its bounds, which no board reaches.
+    pub fn at(&self, row: i32, column: i32) -> Vec<Point> {
+        self.coords
             .iter()
-            .fold(&mut HashMap::new(), |acc, coord| {
-                if let Some(val) = acc.get(&coord.y) {
-                    acc.insert(coord.y, val + 1);
-                } else {
-                    acc.insert(coord.y, 1);
-                };
-
-                acc
+            .map(|p| Point {
+                x: p.x.saturating_add(column),
+                y: p.y.saturating_add(row),
             })
-            .drain()
-            .map(|(_k, v)| v)
-            .max()
-            .unwrap_or(0);
+            .collect()
+    }
 
+    /// Width is the extent of the block along x and height its extent along y, so shapes with
+    /// gaps such as elles and diagonals measure the same as their bounding box.
+    pub fn dimensions(&self) -> Dimension {
+        if self.coords.is_empty() {
+            return Dimension {
+                width: 0,
+                height: 0,
+            };
+        }
+
+        let (min, max) = self.bounding_box();
         Dimension {
-            width: width as usize,
-            height: height as usize,
+            width: (max.x - min.x + 1) as usize,
+            height: (max.y - min.y + 1) as usize,
         }
     }
 
@@ -271,6 +777,191 @@ impl Block {
         });
         self
     }
+
+    /// Rotate 180 degrees about the origin.
+    pub fn rotate_180(&mut self) -> &mut Self {
+        self.coordinates_mut().iter_mut().for_each(|p| {
+            p.mirror_x().mirror_y();
+        });
+        self
+    }
+
+    /// Rotate 90 degrees to the right about the block's centroid, so that it turns in place
+    /// rather than swinging around the origin.
+    ///
+    /// The centroid is rounded to the nearest half cell. Shapes whose centroid falls in the
+    /// middle of a cell edge cannot turn exactly in place, and are rounded down onto the grid.
+    pub fn rotate_right_about_centroid(&mut self) -> &mut Self {
+        if self.coords.is_empty() {
+            return self;
+        }
+
+        // Work at double scale so that centroids between cells land on whole coordinates.
+        let (x, y) = self.center_of_mass();
+        let center = Point {
+            x: (2.0 * x).round() as i32,
+            y: (2.0 * y).round() as i32,
+        };
+
+        self.coordinates_mut().iter_mut().for_each(|p| {
+            let mut doubled = Point {
+                x: 2 * p.x,
+                y: 2 * p.y,
+            };
+            doubled.rotate_right_about(&center);
+            *p = Point {
+                x: doubled.x.div_euclid(2),
+                y: doubled.y.div_euclid(2),
+            };
+        });
+        self
+    }
+
+    /// Average position of the block's cells as `(x, y)`, in its current coordinates. An empty
+    /// block is centered on the origin.
+    pub fn center_of_mass(&self) -> (f32, f32) {
+        if self.coords.is_empty() {
+            return (0.0, 0.0);
+        }
+
+        let count = self.coords.len() as f32;
+        let sum = self.coords.iter().fold(Point::default(), |sum, p| sum + *p);
+        (sum.x as f32 / count, sum.y as f32 / count)
+    }
+
+    /// Rotate by a number of quarter turns, where positive counts turn right and negative counts
+    /// turn left.
+    pub fn rotate_n(&mut self, quarter_turns: i32) -> &mut Self {
+        match quarter_turns.rem_euclid(4) {
+            1 => self.rotate_right(),
+            2 => self.rotate_180(),
+            3 => self.rotate_left(),
+            _ => self,
+        }
+    }
+
+    /// Reflect horizontally, across the y axis.
+    pub fn mirror_x(&mut self) -> &mut Self {
+        self.coordinates_mut().iter_mut().for_each(|p| {
+            p.mirror_x();
+        });
+        self
+    }
+
+    /// Reflect vertically, across the x axis.
+    pub fn mirror_y(&mut self) -> &mut Self {
+        self.coordinates_mut().iter_mut().for_each(|p| {
+            p.mirror_y();
+        });
+        self
+    }
+
+    /// Returns true if every cell can be reached from every other by stepping up, down, left or
+    /// right through the block. Cells that only touch at a corner are not connected, so
+    /// diagonals are not considered connected.
+    pub fn is_connected(&self) -> bool {
+        let Some(start) = self.coords.first() else {
+            return true;
+        };
+
+        let mut reached = vec![*start];
+        let mut frontier = vec![*start];
+        while let Some(p) = frontier.pop() {
+            let neighbors = [
+                Point { x: 1, y: 0 },
+                Point { x: -1, y: 0 },
+                Point { x: 0, y: 1 },
+                Point { x: 0, y: -1 },
+            ];
+            for offset in neighbors {
+                let neighbor = p + offset;
+                if self.coords.contains(&neighbor) && !reached.contains(&neighbor) {
+                    reached.push(neighbor);
+                    frontier.push(neighbor);
+                }
+            }
+        }
+
+        self.coords.iter().all(|p| reached.contains(p))
+    }
+
+    /// Classify the block by how many of its rotations are distinct.
+    pub fn symmetry(&self) -> Symmetry {
+        let original = self.normalized_coordinates();
+        let mut rotated = self.clone();
+
+        // The rotations form a cycle of length 1, 2 or 4, so a shape that is unchanged by a
+        // quarter turn is unchanged by every turn.
+        if rotated.rotate_right().normalized_coordinates() == original {
+            return Symmetry::Full;
+        }
+
+        if rotated.rotate_right().normalized_coordinates() == original {
+            return Symmetry::TwoFold;
+        }
+
+        Symmetry::None
+    }
+
+    /// Each orientation the block can be turned to, normalized and listed once, starting with the
+    /// current one and turning right.
+    pub fn distinct_rotations(&self) -> Vec<Block> {
+        let mut rotations: Vec<Block> = Vec::new();
+        let mut rotated = self.clone();
+
+        for _ in 0..4 {
+            rotated.normalize();
+            let orientation = rotated.normalized_coordinates();
+            if !rotations
+                .iter()
+                .any(|seen| seen.normalized_coordinates() == orientation)
+            {
+                rotations.push(rotated.clone());
+            }
+            rotated.rotate_right();
+        }
+
+        rotations
+    }
+
+    /// The block's shape independent of where it sits and how it is turned: the smallest of its
+    /// four rotations once each is normalized and sorted.
+    pub fn canonical(&self) -> Vec<Point> {
+        let mut rotated = self.clone();
+        let mut canonical = rotated.normalized_coordinates();
+
+        for _ in 0..3 {
+            let candidate = rotated.rotate_right().normalized_coordinates();
+            if candidate < canonical {
+                canonical = candidate;
+            }
+        }
+
+        canonical
+    }
+
+    /// Move every point of the block by `dx` along x and `dy` along y.
+    pub fn translate(&mut self, dx: i32, dy: i32) -> &mut Self {
+        let offset = Point { x: dx, y: dy };
+        self.coordinates_mut().iter_mut().for_each(|p| *p += offset);
+        self
+    }
+
+    /// Translate the block so that it sits flush against (0,0) in the first quadrant.
+    pub fn normalize(&mut self) -> &mut Self {
+        let (min, _) = self.bounding_box();
+        self.coordinates_mut().iter_mut().for_each(|p| *p -= min);
+        self
+    }
+
+    /// Coordinates shifted into the first quadrant and sorted, so that two blocks with the same
+    /// shape in the same orientation compare equal.
+    fn normalized_coordinates(&self) -> Vec<Point> {
+        let mut normalized = self.clone();
+        normalized.normalize();
+        normalized.coords.sort();
+        normalized.coords
+    }
 }
 
 impl Distribution<Block> for StandardUniform {
@@ -280,11 +971,17 @@ impl Distribution<Block> for StandardUniform {
         let height = rng.random::<u8>() as usize % MAX_RECTANGLE_EDGE + 1;
 
         match variant {
-            Variant::Rectangle => Block::rectangle(width, height),
+            // Custom shapes are never sampled, but fall back to a rectangle all the same.
+            Variant::Rectangle | Variant::Custom => Block::rectangle(width, height),
             Variant::Tee => Block::tee(),
             Variant::Elle => Block::elle(width, height),
             Variant::Diagonal => Block::diagonal(width),
             Variant::Line => Block::line(width),
+            Variant::Ess => Block::ess(),
+            Variant::Zed => Block::zed(),
+            Variant::Plus => Block::plus(),
+            #[cfg(feature = "pentominoes")]
+            Variant::Pentomino(shape) => Block::pentomino(shape),
         }
     }
 }
@@ -301,12 +998,11 @@ impl Display for Block {
         // Blocks are encoded assuming a standard coordinate system, where x grows right and y
         // grows up. Printing to stdout naturally occurs top to bottom, so a bit of translation is
         // required.
-        let min_y = self.coordinates().iter().map(|p| p.y).min().unwrap_or(0);
-        let min_x = self.coordinates().iter().map(|p| p.x).min().unwrap_or(0);
+        let (min, _) = self.bounding_box();
         let coord_to_index = |p: &Point| -> usize {
             // normalize all shapes to be in the first quadrant
-            let norm_x = (p.x - min_x) as usize;
-            let norm_y = (p.y - min_y) as usize;
+            let norm_x = (p.x - min.x) as usize;
+            let norm_y = (p.y - min.y) as usize;
 
             display_repr_width * (display_repr_height - 1 - norm_y) + norm_x * 2
         };
@@ -343,6 +1039,33 @@ impl Debug for Block {
     }
 }
 
+impl<'a> IntoIterator for &'a Block {
+    type Item = &'a Point;
+    type IntoIter = std::slice::Iter<'a, Point>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.coords.iter()
+    }
+}
+
+impl PartialEq for Block {
+    /// Blocks are equal when they have the same shape, regardless of their rotation, position or
+    /// variant.
+    fn eq(&self, other: &Self) -> bool {
+        self.canonical() == other.canonical()
+    }
+}
+
+impl Eq for Block {}
+
+impl Hash for Block {
+    /// Hashes the canonical form, so every rotation of a shape lands in the same bucket, in
+    /// keeping with [`PartialEq`].
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.canonical().hash(state);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -366,12 +1089,12 @@ mod tests {
 
     // ┌─┐
     // └─┘
-    test_can_create!(can_create_1x1, 1, 1, vec![Point { x: 0, y: 0 }]);
+    test_can_create!(can_create_1x1, 1, 1, [Point { x: 0, y: 0 }]);
 
     // ┌─┬─┐
     // ├─┼─┤
     // └─┴─┘
-    test_can_create!(can_create_2x2, 2, 2, vec![Point { x: 0, y: 0 }]);
+    test_can_create!(can_create_2x2, 2, 2, [Point { x: 0, y: 0 }]);
 
     // ┌─┬─┐
     // ├─┼─┤
@@ -381,13 +1104,13 @@ mod tests {
         can_create_2x3_rect,
         2,
         3,
-        vec![
+        [
             Point { x: 0, y: 0 },
             Point { x: 0, y: 1 },
             Point { x: 0, y: 2 },
             Point { x: 1, y: 0 },
             Point { x: 1, y: 1 },
-            Point { x: 1, y: 2 },
+            Point { x: 1, y: 2 }
         ]
     );
 
@@ -398,13 +1121,13 @@ mod tests {
         can_create_3x2_rect,
         3,
         2,
-        vec![
+        [
             Point { x: 0, y: 0 },
             Point { x: 0, y: 1 },
             Point { x: 1, y: 0 },
             Point { x: 1, y: 1 },
             Point { x: 2, y: 0 },
-            Point { x: 2, y: 1 },
+            Point { x: 2, y: 1 }
         ]
     );
 
@@ -463,6 +1186,18 @@ mod tests {
     );
     test_dimensions!(test_dimensions_len3_line, Block::line(3), 3, 1);
     test_dimensions!(test_dimensions_len5_line, Block::line(5), 5, 1);
+    test_dimensions!(
+        test_dimensions_len3_horizontal_line,
+        Block::line_horizontal(3),
+        3,
+        1
+    );
+    test_dimensions!(
+        test_dimensions_len3_vertical_line,
+        Block::line_vertical(3),
+        1,
+        3
+    );
     test_dimensions!(test_dimensions_0deg_tee, Block::tee(), 3, 2);
     test_dimensions!(test_dimensions_90deg_tee, Block::tee().rotate_left(), 2, 3);
     test_dimensions!(test_dimensions_3x2_0deg_elle, Block::elle(2, 3), 3, 2);
@@ -479,6 +1214,53 @@ mod tests {
         2,
         2
     );
+    test_dimensions!(test_dimensions_0deg_ess, Block::ess(), 3, 2);
+    test_dimensions!(test_dimensions_90deg_ess, Block::ess().rotate_left(), 2, 3);
+    test_dimensions!(
+        test_dimensions_180deg_ess,
+        Block::ess().rotate_left().rotate_left(),
+        3,
+        2
+    );
+    test_dimensions!(
+        test_dimensions_270deg_ess,
+        Block::ess().rotate_right(),
+        2,
+        3
+    );
+    test_dimensions!(test_dimensions_0deg_zed, Block::zed(), 3, 2);
+    test_dimensions!(test_dimensions_90deg_zed, Block::zed().rotate_left(), 2, 3);
+    test_dimensions!(
+        test_dimensions_180deg_zed,
+        Block::zed().rotate_right().rotate_right(),
+        3,
+        2
+    );
+    test_dimensions!(
+        test_dimensions_270deg_zed,
+        Block::zed().rotate_right(),
+        2,
+        3
+    );
+    test_dimensions!(test_dimensions_0deg_plus, Block::plus(), 3, 3);
+    test_dimensions!(
+        test_dimensions_90deg_plus,
+        Block::plus().rotate_left(),
+        3,
+        3
+    );
+    test_dimensions!(
+        test_dimensions_180deg_plus,
+        Block::plus().rotate_left().rotate_left(),
+        3,
+        3
+    );
+    test_dimensions!(
+        test_dimensions_270deg_plus,
+        Block::plus().rotate_right(),
+        3,
+        3
+    );
     test_dimensions!(test_dimensions_2_0deg_diag, Block::diagonal(2), 2, 2);
     test_dimensions!(
         test_dimensions_2_90deg_diag,
@@ -626,4 +1408,739 @@ mod tests {
         4,
         Block::tee().coordinates()
     );
+
+    macro_rules! test_symmetry {
+        ( $name:ident, $block:expr, $expected:expr ) => {
+            #[test]
+            fn $name() {
+                assert_eq!($expected, $block.symmetry());
+            }
+        };
+    }
+
+    test_symmetry!(symmetry_1x1_rect, Block::rectangle(1, 1), Symmetry::Full);
+    test_symmetry!(symmetry_3x3_rect, Block::rectangle(3, 3), Symmetry::Full);
+    test_symmetry!(symmetry_2x3_rect, Block::rectangle(2, 3), Symmetry::TwoFold);
+    test_symmetry!(symmetry_line, Block::line(4), Symmetry::TwoFold);
+    test_symmetry!(symmetry_diagonal, Block::diagonal(3), Symmetry::TwoFold);
+    test_symmetry!(symmetry_tee, Block::tee(), Symmetry::None);
+    test_symmetry!(symmetry_plus, Block::plus(), Symmetry::Full);
+    test_symmetry!(symmetry_elle, Block::elle(3, 3), Symmetry::None);
+    test_symmetry!(
+        symmetry_rotated_tee,
+        Block::tee().rotate_left(),
+        Symmetry::None
+    );
+
+    #[test]
+    fn can_translate_to_board_origin() {
+        let points = Block::tee().at(2, 3);
+        assert_eq!(
+            vec![
+                Point { x: 3, y: 2 },
+                Point { x: 4, y: 2 },
+                Point { x: 5, y: 2 },
+                Point { x: 4, y: 3 },
+            ],
+            points
+        );
+    }
+
+    #[cfg(feature = "pentominoes")]
+    #[test]
+    fn can_create_pentominoes() {
+        for shape in Pentomino::ALL {
+            let block = Block::pentomino(shape);
+            assert_eq!(5, block.coordinates().len(), "{shape} should have 5 cells");
+        }
+
+        let t = Block::pentomino(Pentomino::T);
+        for p in [
+            Point { x: 1, y: 0 },
+            Point { x: 1, y: 1 },
+            Point { x: 0, y: 2 },
+            Point { x: 1, y: 2 },
+            Point { x: 2, y: 2 },
+        ] {
+            assert!(t.coordinates().contains(&p));
+        }
+    }
+
+    #[cfg(feature = "pentominoes")]
+    test_symmetry!(
+        symmetry_f_pentomino,
+        Block::pentomino(Pentomino::F),
+        Symmetry::None
+    );
+    #[cfg(feature = "pentominoes")]
+    test_symmetry!(
+        symmetry_t_pentomino,
+        Block::pentomino(Pentomino::T),
+        Symmetry::None
+    );
+    #[cfg(feature = "pentominoes")]
+    test_symmetry!(
+        symmetry_x_pentomino,
+        Block::pentomino(Pentomino::X),
+        Symmetry::Full
+    );
+    #[cfg(feature = "pentominoes")]
+    test_symmetry!(
+        symmetry_i_pentomino,
+        Block::pentomino(Pentomino::I),
+        Symmetry::TwoFold
+    );
+    #[cfg(feature = "pentominoes")]
+    test_symmetry!(
+        symmetry_z_pentomino,
+        Block::pentomino(Pentomino::Z),
+        Symmetry::TwoFold
+    );
+
+    #[test]
+    fn bounding_box_includes_gaps() {
+        let tee = Block::tee();
+
+        // the cell beside the tee's stem is in the bounding box but unoccupied
+        let gap = Point { x: 0, y: 1 };
+        assert!(tee.bounding_contains(&gap));
+        assert!(!tee.coordinates().contains(&gap));
+
+        assert!(tee.bounding_contains(&Point { x: 1, y: 1 }));
+        assert!(!tee.bounding_contains(&Point { x: 3, y: 0 }));
+        assert!(!tee.bounding_contains(&Point { x: 1, y: -1 }));
+    }
+
+    #[test]
+    fn zig_zags_are_anchored_and_generated() {
+        for block in [Block::ess(), Block::zed()] {
+            assert!(block.contains(0, 0), "{block}");
+            assert!(block.coordinates().iter().all(|p| p.y >= 0));
+        }
+
+        let standard = Block::standard_set();
+        assert!(standard.iter().any(|b| matches!(b.variant(), Variant::Ess)));
+        assert!(standard.iter().any(|b| matches!(b.variant(), Variant::Zed)));
+    }
+
+    #[test]
+    fn catalog_has_each_distinct_orientation_once() {
+        let catalog = Block::catalog_all_orientations();
+
+        // A 2x3 rectangle is a 3x2 rectangle on its side, so its orientations are already
+        // counted. Likewise the I, V and X pentominoes are a line of five, a 3x3 elle and a plus.
+        #[allow(unused_mut)]
+        let mut repeated = vec![Block::rectangle(2, 3)];
+        #[cfg(feature = "pentominoes")]
+        repeated.extend([
+            Block::pentomino(Pentomino::I),
+            Block::pentomino(Pentomino::V),
+            Block::pentomino(Pentomino::X),
+        ]);
+
+        let rotations = |blocks: &[Block]| -> usize {
+            blocks
+                .iter()
+                .map(|block| block.symmetry().distinct_rotations())
+                .sum()
+        };
+        let expected = rotations(&Block::standard_set()) - rotations(&repeated);
+        assert_eq!(expected, catalog.len());
+
+        for (i, a) in catalog.iter().enumerate() {
+            for b in &catalog[i + 1..] {
+                assert_ne!(
+                    a.normalized_coordinates(),
+                    b.normalized_coordinates(),
+                    "Duplicate orientation in catalog:\n{a}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn can_create_vertical_line() {
+        assert_eq!(
+            &vec![
+                Point { x: 0, y: 0 },
+                Point { x: 0, y: 1 },
+                Point { x: 0, y: 2 },
+            ],
+            Block::line_vertical(3).coordinates()
+        );
+    }
+
+    #[test]
+    fn awkward_blocks_weigh_more() {
+        assert!(Block::tee().placement_weight() > Block::rectangle(1, 1).placement_weight());
+        assert!(Block::tee().placement_weight() > Block::rectangle(2, 2).placement_weight());
+        assert!(Block::elle(2, 2).placement_weight() > Block::line(3).placement_weight());
+        assert!(Block::line(3).placement_weight() > Block::line(2).placement_weight());
+    }
+
+    #[test]
+    fn can_mirror_elle() {
+        let mut elle = Block::elle(2, 3);
+        elle.mirror_x();
+        assert_eq!(
+            &vec![
+                Point { x: 0, y: 0 },
+                Point { x: -1, y: 0 },
+                Point { x: -2, y: 0 },
+                Point { x: 0, y: 1 },
+            ],
+            elle.coordinates()
+        );
+
+        elle.mirror_x().mirror_y();
+        assert_eq!(
+            &vec![
+                Point { x: 0, y: 0 },
+                Point { x: 1, y: 0 },
+                Point { x: 2, y: 0 },
+                Point { x: 0, y: -1 },
+            ],
+            elle.coordinates()
+        );
+    }
+
+    #[test]
+    fn mirroring_twice_restores_block() {
+        let original = Block::elle(3, 2);
+        let mut mirrored = original.clone();
+
+        mirrored.mirror_x().mirror_x();
+        assert_eq!(original.coordinates(), mirrored.coordinates());
+
+        mirrored.mirror_y().mirror_y();
+        assert_eq!(original.coordinates(), mirrored.coordinates());
+    }
+
+    #[test]
+    fn rotate_n_matches_repeated_rotations() {
+        for turns in -8..=8i32 {
+            let mut expected = Block::elle(3, 2);
+            for _ in 0..turns.abs() {
+                if turns > 0 {
+                    expected.rotate_right();
+                } else {
+                    expected.rotate_left();
+                }
+            }
+
+            let mut rotated = Block::elle(3, 2);
+            rotated.rotate_n(turns);
+            assert_eq!(
+                expected.coordinates(),
+                rotated.coordinates(),
+                "turns: {turns}"
+            );
+        }
+    }
+
+    #[test]
+    fn rotate_n_takes_the_short_way_around() {
+        let mut left = Block::tee();
+        left.rotate_left();
+        assert_eq!(left.coordinates(), Block::tee().rotate_n(-1).coordinates());
+
+        let mut right = Block::tee();
+        right.rotate_right();
+        assert_eq!(right.coordinates(), Block::tee().rotate_n(5).coordinates());
+    }
+
+    #[test]
+    fn normalize_moves_block_into_first_quadrant() {
+        let mut tee = Block::tee();
+        tee.rotate_right();
+        assert!(tee.coordinates().iter().any(|p| p.x < 0 || p.y < 0));
+
+        tee.normalize();
+        assert!(tee.coordinates().iter().all(|p| p.x >= 0 && p.y >= 0));
+        assert_eq!(
+            &vec![
+                Point { x: 0, y: 2 },
+                Point { x: 0, y: 1 },
+                Point { x: 0, y: 0 },
+                Point { x: 1, y: 1 },
+            ],
+            tee.coordinates()
+        );
+    }
+
+    #[test]
+    fn normalize_is_idempotent() {
+        let mut elle = Block::elle(3, 3);
+        elle.rotate_left().normalize();
+        let once = elle.coordinates().clone();
+
+        elle.normalize();
+        assert_eq!(&once, elle.coordinates());
+    }
+
+    #[test]
+    fn rotations_of_a_block_are_equal() {
+        let tee = Block::tee();
+        let mut rotated = Block::tee();
+        for _ in 0..4 {
+            rotated.rotate_left();
+            assert_eq!(tee, rotated);
+        }
+
+        let mut line = Block::line(2);
+        line.rotate_left();
+        assert_eq!(Block::line(2), line);
+    }
+
+    #[test]
+    fn different_shapes_are_not_equal() {
+        assert_ne!(Block::tee(), Block::elle(2, 2));
+        assert_ne!(Block::tee(), Block::elle(3, 2));
+        assert_ne!(Block::ess(), Block::zed());
+    }
+
+    #[test]
+    fn rotations_hash_identically() {
+        let mut seen = std::collections::HashSet::new();
+        let mut elle = Block::elle(3, 2);
+        for _ in 0..4 {
+            seen.insert(elle.clone());
+            elle.rotate_right();
+        }
+
+        assert_eq!(1, seen.len());
+
+        let mut shuffled = Block::elle(3, 2);
+        shuffled.coordinates_mut().reverse();
+        assert!(seen.contains(&shuffled));
+
+        seen.insert(Block::tee());
+        assert_eq!(2, seen.len());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip_preserves_block() {
+        let mut tee = Block::tee();
+        tee.rotate_right();
+
+        let json = serde_json::to_string(&tee).unwrap();
+        let restored: Block = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(tee, restored);
+        assert_eq!(tee.coordinates(), restored.coordinates());
+        assert!(matches!(restored.variant, Variant::Tee));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn deserialized_shape_can_be_placed() {
+        let json = r#"{"coords":[{"x":-1,"y":-1},{"x":0,"y":0}],"variant":"Diagonal"}"#;
+        let block: Block = serde_json::from_str(json).unwrap();
+
+        let canvas = crate::canvas::Canvas::new(4, 4);
+        assert!(canvas.can_fit(&block).is_some());
+        assert!(!canvas.can_fit_at(&block, 0, 0));
+        assert!(canvas.can_fit_at(&block, 1, 1));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn deserializing_rejects_unusable_shapes() {
+        for json in [
+            r#"{"coords":[],"variant":"Custom"}"#,
+            r#"{"coords":[{"x":0,"y":0},{"x":0,"y":0}],"variant":"Custom"}"#,
+            r#"{"coords":[{"x":0,"y":0},{"x":2147483647,"y":0}],"variant":"Custom"}"#,
+        ] {
+            assert!(serde_json::from_str::<Block>(json).is_err(), "{json}");
+        }
+    }
+
+    #[test]
+    fn constructors_use_variant_color() {
+        assert_eq!(1, Block::rectangle(2, 2).color());
+        assert_eq!(2, Block::tee().color());
+        assert_eq!(3, Block::diagonal(3).color());
+        assert_eq!(4, Block::elle(2, 3).color());
+        assert_eq!(5, Block::line(4).color());
+        assert_eq!(5, Block::line_vertical(4).color());
+        assert_eq!(6, Block::ess().color());
+        assert_eq!(7, Block::zed().color());
+        assert_eq!(8, Block::plus().color());
+    }
+
+    #[test]
+    fn with_color_overrides_default() {
+        let mut tee = Block::tee().with_color(42);
+        assert_eq!(42, tee.color());
+
+        tee.rotate_right().normalize();
+        assert_eq!(42, tee.color());
+    }
+
+    #[test]
+    fn area_counts_cells() {
+        assert_eq!(9, Block::rectangle(3, 3).area());
+        assert_eq!(4, Block::tee().area());
+
+        let mut elle = Block::elle(3, 2);
+        let area = elle.area();
+        elle.rotate_right();
+        assert_eq!(area, elle.area());
+        elle.mirror_x();
+        assert_eq!(area, elle.area());
+        assert_eq!(elle.cell_count(), elle.area());
+    }
+
+    #[test]
+    fn can_find_bounding_box() {
+        assert_eq!(
+            (Point { x: 0, y: 0 }, Point { x: 2, y: 1 }),
+            Block::tee().bounding_box()
+        );
+
+        let mut elle = Block::elle(2, 3);
+        assert_eq!(
+            (Point { x: 0, y: 0 }, Point { x: 2, y: 1 }),
+            elle.bounding_box()
+        );
+        elle.rotate_right();
+        assert_eq!(
+            (Point { x: 0, y: -2 }, Point { x: 1, y: 0 }),
+            elle.bounding_box()
+        );
+
+        let mut diagonal = Block::diagonal(3);
+        diagonal.rotate_left().rotate_left();
+        assert_eq!(
+            (Point { x: -2, y: -2 }, Point { x: 0, y: 0 }),
+            diagonal.bounding_box()
+        );
+    }
+
+    #[test]
+    fn dimensions_follow_coordinate_extents() {
+        let point = |x, y| Point { x, y };
+        let gapped = Block::from_coords(vec![point(0, 0), point(2, 0)]).unwrap();
+        let hook =
+            Block::from_coords(vec![point(0, 0), point(0, 1), point(1, 1), point(3, 1)]).unwrap();
+        let mut ess = Block::ess();
+        ess.rotate_right();
+
+        for (block, width, height) in [
+            (Block::elle(2, 3), 3, 2),
+            (Block::diagonal(3), 3, 3),
+            (Block::plus(), 3, 3),
+            (gapped, 3, 1),
+            (hook, 4, 2),
+            (ess, 2, 3),
+        ] {
+            let dimensions = block.dimensions();
+            assert_eq!(
+                (width, height),
+                (dimensions.width, dimensions.height),
+                "{block:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn translate_is_reversible() {
+        let original = Block::tee();
+        let mut moved = original.clone();
+
+        moved.translate(3, -2);
+        assert!(moved.coordinates().contains(&Point { x: 4, y: -1 }));
+
+        moved.translate(-3, 2);
+        assert_eq!(original.coordinates(), moved.coordinates());
+    }
+
+    #[test]
+    fn translate_composes_with_rotation() {
+        // Rotating after a translation also rotates the offset about the origin.
+        let mut translated_first = Block::elle(2, 3);
+        translated_first.translate(1, 0).rotate_right();
+
+        let mut rotated_first = Block::elle(2, 3);
+        rotated_first.rotate_right().translate(0, -1);
+
+        assert_eq!(translated_first.coordinates(), rotated_first.coordinates());
+    }
+
+    #[test]
+    fn contains_checks_occupied_cells() {
+        let mut tee = Block::tee();
+        assert!(tee.contains(1, 1));
+        assert!(!tee.contains(0, 1));
+
+        tee.rotate_right();
+        assert!(!tee.contains(1, 1));
+        assert!(tee.contains(1, -1));
+
+        let elle = Block::elle(3, 3);
+        assert!(elle.bounding_contains(&Point { x: 1, y: 1 }));
+        assert!(!elle.contains(1, 1));
+        assert!(elle.contains(0, 2));
+    }
+
+    #[test]
+    fn can_add_and_subtract_points() {
+        let a = Point { x: 2, y: -3 };
+        let b = Point { x: -5, y: 1 };
+
+        assert_eq!(Point { x: -3, y: -2 }, a + b);
+        assert_eq!(Point { x: 7, y: -4 }, a - b);
+        assert_eq!(a, a + b - b);
+
+        let mut c = a;
+        c += b;
+        assert_eq!(Point { x: -3, y: -2 }, c);
+        c -= b;
+        assert_eq!(a, c);
+    }
+
+    #[test]
+    fn can_rotate_point_about_center() {
+        let center = Point { x: 2, y: 1 };
+
+        let mut p = Point { x: 3, y: 1 };
+        p.rotate_right_about(&center);
+        assert_eq!(Point { x: 2, y: 0 }, p);
+
+        p.rotate_left_about(&center);
+        assert_eq!(Point { x: 3, y: 1 }, p);
+
+        let mut c = center;
+        c.rotate_left_about(&center);
+        assert_eq!(center, c);
+    }
+
+    #[test]
+    fn square_rotates_in_place_about_centroid() {
+        for size in [2, 3] {
+            let original = Block::rectangle(size, size);
+            let mut rotated = original.clone();
+            rotated.rotate_right_about_centroid();
+
+            let mut expected = original.coordinates().clone();
+            let mut actual = rotated.coordinates().clone();
+            expected.sort();
+            actual.sort();
+            assert_eq!(expected, actual);
+        }
+    }
+
+    #[test]
+    fn line_rotates_about_middle_cell() {
+        let mut line = Block::line(3);
+        line.rotate_right_about_centroid();
+        assert_eq!(
+            &vec![
+                Point { x: 1, y: 1 },
+                Point { x: 1, y: 0 },
+                Point { x: 1, y: -1 },
+            ],
+            line.coordinates()
+        );
+    }
+
+    #[test]
+    fn can_iterate_over_block() {
+        let line = Block::line(4);
+        let mut sum = 0;
+        for p in &line {
+            sum += p.x;
+        }
+        assert_eq!(6, sum);
+        assert_eq!(line.coordinates().len(), (&line).into_iter().count());
+    }
+
+    #[test]
+    fn can_create_custom_block() {
+        let block = Block::from_coords(vec![
+            Point { x: -1, y: 3 },
+            Point { x: 0, y: 3 },
+            Point { x: 0, y: 4 },
+            Point { x: 0, y: 5 },
+        ])
+        .unwrap();
+
+        assert!(matches!(block.variant, Variant::Custom));
+        assert_eq!(
+            &vec![
+                Point { x: 0, y: 0 },
+                Point { x: 1, y: 0 },
+                Point { x: 1, y: 1 },
+                Point { x: 1, y: 2 },
+            ],
+            block.coordinates()
+        );
+        assert_eq!(Block::elle(2, 3), block);
+    }
+
+    #[test]
+    fn custom_block_rejects_bad_coordinates() {
+        assert_eq!(Some(BlockError::Empty), Block::from_coords(vec![]).err());
+        assert_eq!(
+            Some(BlockError::DuplicatePoint(Point { x: 1, y: 0 })),
+            Block::from_coords(vec![
+                Point { x: 0, y: 0 },
+                Point { x: 1, y: 0 },
+                Point { x: 1, y: 0 },
+            ])
+            .err()
+        );
+    }
+
+    #[test]
+    fn diagonal_length_is_clamped() {
+        assert_eq!(2, Block::diagonal(0).cell_count());
+        assert_eq!(2, Block::diagonal(1).cell_count());
+        assert_eq!(
+            MAX_DIAGONAL_LENGTH,
+            Block::diagonal(MAX_DIAGONAL_LENGTH).cell_count()
+        );
+        assert_eq!(MAX_DIAGONAL_LENGTH, Block::diagonal(1000).cell_count());
+    }
+
+    #[test]
+    fn can_check_connectivity() {
+        assert!(Block::tee().is_connected());
+        assert!(Block::elle(3, 3).rotate_left().is_connected());
+        assert!(!Block::diagonal(2).is_connected());
+
+        let split = Block::from_coords(vec![Point { x: 0, y: 0 }, Point { x: 2, y: 0 }]).unwrap();
+        assert!(!split.is_connected());
+    }
+
+    #[test]
+    fn rotate_180_matches_two_quarter_turns() {
+        let mut expected = Block::tee();
+        expected.rotate_right().rotate_right();
+
+        let mut rotated = Block::tee();
+        rotated.rotate_180();
+        assert_eq!(expected.coordinates(), rotated.coordinates());
+
+        rotated.rotate_180();
+        assert_eq!(Block::tee().coordinates(), rotated.coordinates());
+    }
+
+    #[test]
+    fn diagonal_dimensions_follow_its_cells() {
+        let mut rotated = Block::diagonal(3);
+        rotated.rotate_left();
+        assert_eq!(
+            (3, 3),
+            (rotated.dimensions().width, rotated.dimensions().height)
+        );
+
+        let mut translated = Block::diagonal(4);
+        translated.translate(5, -2);
+        assert_eq!(
+            (4, 4),
+            (
+                translated.dimensions().width,
+                translated.dimensions().height
+            )
+        );
+
+        // a diagonal that has lost a cell is no longer as long as it was built
+        translated.coordinates_mut().pop();
+        assert_eq!(
+            (3, 3),
+            (
+                translated.dimensions().width,
+                translated.dimensions().height
+            )
+        );
+    }
+
+    #[test]
+    fn lists_each_distinct_rotation_once() {
+        assert_eq!(1, Block::rectangle(2, 2).distinct_rotations().len());
+        assert_eq!(2, Block::line(3).distinct_rotations().len());
+        assert_eq!(4, Block::tee().distinct_rotations().len());
+
+        for block in Block::standard_set() {
+            let rotations = block.distinct_rotations();
+            assert_eq!(block.symmetry().distinct_rotations(), rotations.len());
+            assert_eq!(
+                block.normalized_coordinates(),
+                rotations[0].normalized_coordinates()
+            );
+            for rotation in &rotations {
+                assert_eq!(block.canonical(), rotation.canonical());
+                assert_eq!(Point::default(), rotation.bounding_box().0);
+            }
+        }
+    }
+
+    #[test]
+    fn center_of_mass_averages_cells() {
+        assert_eq!((1.0, 1.0), Block::rectangle(3, 3).center_of_mass());
+
+        // the corner cell pulls the centroid towards the origin
+        let elle = Block::elle(3, 3);
+        let (x, y) = elle.center_of_mass();
+        assert_eq!((0.6, 0.6), (x, y));
+        assert!(x < 1.0 && y < 1.0);
+
+        let mut moved = elle.clone();
+        moved.rotate_right().translate(2, 0);
+        let (x, y) = moved.center_of_mass();
+        assert!((x - 2.6).abs() < 1e-6 && (y + 0.6).abs() < 1e-6, "{x}, {y}");
+    }
+
+    #[cfg(feature = "pentominoes")]
+    #[test]
+    fn pentominoes_share_one_weight() {
+        let total: f32 = Pentomino::ALL
+            .into_iter()
+            .map(|shape| Block::pentomino(shape).standard_set_weight())
+            .sum();
+        assert!((total - 1.0).abs() < 1e-5);
+        assert_eq!(1.0, Block::tee().standard_set_weight());
+    }
+
+    #[test]
+    fn far_points_are_rejected() {
+        let far = Point {
+            x: 0,
+            y: -MAX_COORDINATE - 1,
+        };
+        assert_eq!(
+            Some(BlockError::OutOfRange(far)),
+            Block::from_coords(vec![Point { x: 0, y: 0 }, far]).err()
+        );
+        assert!(
+            Block::from_coords(vec![Point {
+                x: MAX_COORDINATE,
+                y: 0
+            }])
+            .is_ok()
+        );
+    }
+
+    #[test]
+    fn placing_near_the_edge_of_i32_does_not_fit() {
+        let canvas = crate::canvas::Canvas::new(4, 4);
+        let tee = Block::tee();
+        assert!(!canvas.can_fit_at(&tee, i32::MAX, i32::MAX));
+        assert!(!canvas.can_fit_at(&tee, 0, i32::MAX - 1));
+        assert!(canvas.try_make_playable(&tee, i32::MAX, 0).is_none());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn blocks_saved_without_color_get_the_variant_default() {
+        let json = r#"{"coords":[{"x":0,"y":0},{"x":1,"y":0}],"variant":"Line"}"#;
+        let block: Block = serde_json::from_str(json).unwrap();
+        assert_eq!(Variant::Line.default_color(), block.color());
+
+        let recolored = Block::line(2).with_color(9);
+        let json = serde_json::to_string(&recolored).unwrap();
+        assert_eq!(9, serde_json::from_str::<Block>(&json).unwrap().color());
+    }
 }
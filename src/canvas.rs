@@ -1,12 +1,45 @@
 use std::fmt;
+use std::hash::{Hash, Hasher};
 
 use crate::block::Block;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum PointStatus {
-    Occupied,
+    /// Filled by a block, tagged with that block's id. Placed blocks use their color as the id,
+    /// while cells filled any other way use 0.
+    Occupied(u8),
     Empty,
-    MarkedForRemoval,
+    /// Part of a completed line, waiting to be swept after the given number of
+    /// [`Canvas::tick_marks`].
+    MarkedForRemoval(u8),
+}
+
+impl PointStatus {
+    /// ANSI escape sequence that styles the point in a terminal: occupied cells in a color picked
+    /// by their block id, empty cells dim and marked cells bold.
+    pub fn ansi_style(&self) -> &'static str {
+        // Bright red, green, yellow, blue, magenta and cyan.
+        const BLOCK_COLORS: [&str; 6] = [
+            "\x1b[91m", "\x1b[92m", "\x1b[93m", "\x1b[94m", "\x1b[95m", "\x1b[96m",
+        ];
+
+        match self {
+            PointStatus::Occupied(0) => "\x1b[37m",
+            PointStatus::Occupied(id) => BLOCK_COLORS[(*id as usize - 1) % BLOCK_COLORS.len()],
+            PointStatus::Empty => "\x1b[2m",
+            PointStatus::MarkedForRemoval(_) => "\x1b[1m",
+        }
+    }
+
+    /// Single character used when drawing the point.
+    pub fn as_char(&self) -> char {
+        match self {
+            PointStatus::Occupied(_) => '▅',
+            PointStatus::MarkedForRemoval(_) => '⏲',
+            PointStatus::Empty => '.',
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -16,14 +49,223 @@ pub struct PlayableBlock {
     column: i32,
 }
 
+impl PlayableBlock {
+    /// The block, in the orientation it will be placed.
+    pub fn block(&self) -> &Block {
+        &self.block
+    }
+
+    /// Palette index of the block being placed, for drawing the cells it will occupy.
+    pub fn color(&self) -> u8 {
+        self.block.color()
+    }
+}
+
+/// One cell of a [`Canvas::snapshot`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CellInfo {
+    /// Whether the cell is filled, including cells marked for removal.
+    pub occupied: bool,
+    /// Id of the block filling the cell, or `None` if it is empty or marked for removal.
+    pub block_id: Option<u8>,
+}
+
+/// Indices of the completed rows and columns found on a canvas.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ClearedLines {
+    pub rows: Vec<usize>,
+    pub columns: Vec<usize>,
+}
+
+impl ClearedLines {
+    /// Total number of lines, rows and columns combined.
+    pub fn total(&self) -> usize {
+        self.rows.len() + self.columns.len()
+    }
+}
+
+/// Reasons a text grid cannot be read by [`Canvas::from_str_grid`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParseCanvasError {
+    /// The text contains no rows of cells.
+    Empty,
+    /// A row, counted from the top starting at 0, has a different number of cells than the first.
+    RaggedRow {
+        row: usize,
+        expected: usize,
+        found: usize,
+    },
+    /// A character that does not describe a cell.
+    UnexpectedChar(char),
+}
+
+impl fmt::Display for ParseCanvasError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseCanvasError::Empty => write!(f, "no rows found"),
+            ParseCanvasError::RaggedRow {
+                row,
+                expected,
+                found,
+            } => write!(f, "row {row} has {found} cells, expected {expected}"),
+            ParseCanvasError::UnexpectedChar(c) => write!(f, "unexpected character {c:?}"),
+        }
+    }
+}
+
+impl std::error::Error for ParseCanvasError {}
+
+/// Reasons a block cannot be placed, see [`Canvas::make_playable`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum PlaceError {
+    /// Part of the block would hang off the edge of the board.
+    OutOfBounds,
+    /// Part of the block would cover an occupied cell.
+    Overlap,
+}
+
+impl fmt::Display for PlaceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PlaceError::OutOfBounds => write!(f, "block would leave the board"),
+            PlaceError::Overlap => write!(f, "block would cover an occupied cell"),
+        }
+    }
+}
+
+impl std::error::Error for PlaceError {}
+
+/// One bit per cell of a [`Canvas`], indexed the same as [`Canvas::contents`].
+#[derive(Debug, Clone, Default, PartialEq)]
+struct Bits {
+    words: Vec<u64>,
+}
+
+impl Bits {
+    fn new(len: usize) -> Self {
+        Bits {
+            words: vec![0; len.div_ceil(64)],
+        }
+    }
+
+    fn get(&self, index: usize) -> bool {
+        self.words[index / 64] & (1 << (index % 64)) != 0
+    }
+
+    fn assign(&mut self, index: usize, value: bool) {
+        let bit = 1 << (index % 64);
+        if value {
+            self.words[index / 64] |= bit;
+        } else {
+            self.words[index / 64] &= !bit;
+        }
+    }
+
+    fn count_ones(&self) -> usize {
+        self.words
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+
+    fn none(&self) -> bool {
+        self.words.iter().all(|&word| word == 0)
+    }
+
+    /// Returns true if the `len` bits starting at `start` are all set.
+    fn all_in(&self, start: usize, len: usize) -> bool {
+        let end = start + len;
+        let mut index = start;
+        while index < end {
+            let offset = index % 64;
+            let count = (64 - offset).min(end - index);
+            let mask = (u64::MAX >> (64 - count)) << offset;
+            if self.words[index / 64] & mask != mask {
+                return false;
+            }
+            index += count;
+        }
+
+        true
+    }
+}
+
 /// Canvas holds the state of the board.
+///
+/// Every method which addresses the board, whether placing a block or a single cell, takes
+/// `row, column` in that order, with row 0 at the bottom. Only a block's own
+/// [`crate::block::Point`]s are given as `x, y`, where `x` is the column.
 #[derive(Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "CanvasData", into = "CanvasData")
+)]
 pub struct Canvas {
     pub columns: usize,
     pub rows: usize,
+    /// One byte per cell: the block id of an occupied cell, the remaining dwell of a cell marked
+    /// for removal, or 0 for an empty one. `filled` and `marked` tell which.
+    cells: Vec<u8>,
+    /// Cells which are not empty.
+    filled: Bits,
+    /// Cells marked for removal, a subset of `filled`.
+    marked: Bits,
+    generation: u64,
+}
+
+/// The serialized form of a [`Canvas`], checked before it becomes one so that a corrupt save
+/// cannot cause out of bounds indexing later.
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct CanvasData {
+    columns: usize,
+    rows: usize,
     contents: Vec<PointStatus>,
 }
 
+#[cfg(feature = "serde")]
+impl TryFrom<CanvasData> for Canvas {
+    type Error = String;
+
+    fn try_from(data: CanvasData) -> Result<Self, Self::Error> {
+        // Cells are addressed with `i32` coordinates, so larger boards could never be played.
+        let cells = data
+            .rows
+            .checked_mul(data.columns)
+            .filter(|_| data.rows <= i32::MAX as usize && data.columns <= i32::MAX as usize)
+            .ok_or_else(|| format!("a {}x{} canvas is too large", data.rows, data.columns))?;
+
+        if data.contents.len() != cells {
+            return Err(format!(
+                "expected {} cells for a {}x{} canvas, found {}",
+                cells,
+                data.rows,
+                data.columns,
+                data.contents.len()
+            ));
+        }
+
+        Ok(Canvas::from_contents(
+            data.rows,
+            data.columns,
+            data.contents,
+        ))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<Canvas> for CanvasData {
+    fn from(canvas: Canvas) -> Self {
+        CanvasData {
+            columns: canvas.columns,
+            rows: canvas.rows,
+            contents: canvas.contents(),
+        }
+    }
+}
+
 pub const DEFAULT_CANVAS_HEIGHT: usize = 8;
 pub const DEFAULT_CANVAS_WIDTH: usize = 8;
 
@@ -33,21 +275,339 @@ impl Canvas {
         Canvas {
             columns,
             rows,
-            contents: vec![PointStatus::Empty; usize::from(rows * columns)],
+            cells: vec![0; rows * columns],
+            filled: Bits::new(rows * columns),
+            marked: Bits::new(rows * columns),
+            generation: 0,
+        }
+    }
+
+    fn from_contents(rows: usize, columns: usize, contents: Vec<PointStatus>) -> Self {
+        let mut canvas = Canvas::new(rows, columns);
+        for (index, status) in contents.into_iter().enumerate() {
+            canvas.put(index, status);
+        }
+        canvas
+    }
+
+    /// Create an empty board with the same number of rows and columns.
+    pub fn square(size: usize) -> Self {
+        Canvas::new(size, size)
+    }
+
+    /// Read a board from text, one line per row with the bottom row last. `▅` or `#` is an
+    /// occupied cell, `.` an empty one and `⏲` a cell marked for removal on the next tick.
+    ///
+    /// Whitespace between cells is ignored, as are the row and column labels printed by the
+    /// `Debug` impl, so its output can be read back in.
+    pub fn from_str_grid(s: &str) -> Result<Canvas, ParseCanvasError> {
+        let mut grid: Vec<Vec<PointStatus>> = Vec::new();
+
+        for line in s.lines() {
+            let mut tokens: Vec<&str> = line.split_whitespace().collect();
+            // Skip blank lines and the column labels, one digit per column counting up from 0.
+            let is_column_label = |(i, token): (usize, &&str)| **token == (i % 10).to_string();
+            if tokens.iter().enumerate().all(is_column_label) {
+                continue;
+            }
+            // Drop a row label: a number set apart from the cells that follow it.
+            if tokens.len() > 1 && tokens[0].chars().all(|c| c.is_ascii_digit()) {
+                tokens.remove(0);
+            }
+
+            let row = tokens
+                .into_iter()
+                .flat_map(str::chars)
+                .map(|c| match c {
+                    '▅' | '#' => Ok(PointStatus::Occupied(0)),
+                    '.' => Ok(PointStatus::Empty),
+                    '⏲' => Ok(PointStatus::MarkedForRemoval(1)),
+                    _ => Err(ParseCanvasError::UnexpectedChar(c)),
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+
+            if let Some(first) = grid.first()
+                && first.len() != row.len()
+            {
+                return Err(ParseCanvasError::RaggedRow {
+                    row: grid.len(),
+                    expected: first.len(),
+                    found: row.len(),
+                });
+            }
+            grid.push(row);
+        }
+
+        let Some(columns) = grid.first().map(Vec::len) else {
+            return Err(ParseCanvasError::Empty);
+        };
+
+        let rows = grid.len();
+        Ok(Canvas::from_contents(
+            rows,
+            columns,
+            grid.into_iter().rev().flatten().collect(),
+        ))
+    }
+
+    /// Returns the status of each point on the canvas, in row-major order starting from the
+    /// bottom row.
+    pub fn contents(&self) -> Vec<PointStatus> {
+        (0..self.cells.len()).map(|i| self.status(i)).collect()
+    }
+
+    /// Status of the cell at `index`, rebuilt from the bitsets and its byte.
+    fn status(&self, index: usize) -> PointStatus {
+        if self.marked.get(index) {
+            PointStatus::MarkedForRemoval(self.cells[index])
+        } else if self.filled.get(index) {
+            PointStatus::Occupied(self.cells[index])
+        } else {
+            PointStatus::Empty
+        }
+    }
+
+    /// Statuses of the cells in `row`, from left to right.
+    fn row_statuses(&self, row: usize) -> impl Iterator<Item = PointStatus> + '_ {
+        (self.columns * row..self.columns * (row + 1)).map(|i| self.status(i))
+    }
+
+    /// Returns the board as a grid of characters, indexed `[row][column]` with the bottom row
+    /// first.
+    pub fn to_char_grid(&self) -> Vec<Vec<char>> {
+        (0..self.rows)
+            .map(|row| self.row_statuses(row).map(|p| p.as_char()).collect())
+            .collect()
+    }
+
+    /// Each cell's row, column and status, in the same order as [`Canvas::contents`].
+    pub fn iter_cells(&self) -> impl Iterator<Item = (i32, i32, PointStatus)> + '_ {
+        let columns = self.columns.max(1);
+        (0..self.cells.len())
+            .map(move |i| ((i / columns) as i32, (i % columns) as i32, self.status(i)))
+    }
+
+    /// The board drawn for a terminal, top row first, with each cell colored by
+    /// [`PointStatus::ansi_style`]. Without `color` the escape codes are left out, for output
+    /// which is not a terminal. This is a runtime choice rather than a cargo feature, so one build
+    /// can draw both to a terminal and to a log.
+    pub fn render_ansi(&self, color: bool) -> String {
+        let mut rendered = String::new();
+        for row in (0..self.rows).rev() {
+            for (col, status) in self.row_statuses(row).enumerate() {
+                if col > 0 {
+                    rendered.push(' ');
+                }
+                if color {
+                    rendered.push_str(status.ansi_style());
+                    rendered.push(status.as_char());
+                    rendered.push_str("\x1b[0m");
+                } else {
+                    rendered.push(status.as_char());
+                }
+            }
+            rendered.push('\n');
+        }
+
+        rendered
+    }
+
+    /// Every cell as plain data for external renderers, indexed `[row][column]` with the bottom
+    /// row first.
+    pub fn snapshot(&self) -> Vec<Vec<CellInfo>> {
+        (0..self.rows)
+            .map(|row| {
+                self.row_statuses(row)
+                    .map(|status| CellInfo {
+                        occupied: !matches!(status, PointStatus::Empty),
+                        block_id: match status {
+                            PointStatus::Occupied(id) => Some(id),
+                            _ => None,
+                        },
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Counter that increases whenever the canvas is modified, so renderers can skip redrawing
+    /// an unchanged board.
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// Occupancy of every cell as `1.0` (occupied or marked for removal) or `0.0` (empty), for
+    /// use as e.g. neural network input.
+    ///
+    /// Cells are in row-major order starting from the bottom row, so the cell at `column`/`row`
+    /// is at index `row * columns + column`.
+    pub fn as_f32_grid(&self) -> Vec<f32> {
+        (0..self.cells.len())
+            .map(|i| if self.filled.get(i) { 1.0 } else { 0.0 })
+            .collect()
+    }
+
+    /// Number of cells that are filled, including cells marked for removal.
+    pub fn count_occupied(&self) -> usize {
+        self.filled.count_ones()
+    }
+
+    /// Number of empty cells with an occupied cell somewhere above them in the same column.
+    pub fn count_holes(&self) -> usize {
+        let mut holes = 0;
+
+        for column in 0..self.columns {
+            let mut covered = false;
+            for row in (0..self.rows).rev() {
+                if self.filled.get(self.columns * row + column) {
+                    covered = true;
+                } else {
+                    holes += usize::from(covered);
+                }
+            }
+        }
+
+        holes
+    }
+
+    /// Height of each column from left to right: one more than the row of its highest occupied
+    /// cell, or 0 if the column is empty.
+    pub fn column_heights(&self) -> Vec<usize> {
+        (0..self.columns)
+            .map(|column| {
+                (0..self.rows)
+                    .rev()
+                    .find(|row| self.filled.get(self.columns * row + column))
+                    .map_or(0, |row| row + 1)
+            })
+            .collect()
+    }
+
+    /// Sum of every [`Canvas::column_heights`].
+    pub fn aggregate_height(&self) -> usize {
+        self.column_heights().iter().sum()
+    }
+
+    /// Sum of the height differences between each pair of neighboring columns, where a flat
+    /// surface scores 0.
+    pub fn bumpiness(&self) -> usize {
+        self.column_heights()
+            .windows(2)
+            .map(|pair| pair[0].abs_diff(pair[1]))
+            .sum()
+    }
+
+    /// Fraction of the board that is filled, from `0.0` (empty) to `1.0` (full).
+    pub fn occupancy(&self) -> f32 {
+        if self.cells.is_empty() {
+            return 0.0;
         }
+
+        self.count_occupied() as f32 / self.cells.len() as f32
     }
 
-    /// Returns a the status for each point on the canvas.
-    pub fn contents(&self) -> &Vec<PointStatus> {
-        &self.contents
+    /// Which cells are filled, one bit per cell. Unlike the board itself this ignores block ids
+    /// and marks, so two boards of the same size with the same cells filled share a key.
+    pub(crate) fn occupancy_key(&self) -> &[u64] {
+        &self.filled.words
+    }
+
+    /// Returns true if every cell is filled, including cells marked for removal.
+    pub fn is_full(&self) -> bool {
+        self.count_occupied() == self.cells.len()
+    }
+
+    /// Returns true if every cell is empty.
+    pub fn is_empty(&self) -> bool {
+        self.filled.none()
+    }
+
+    /// Change the size of the board, keeping every cell that is still in bounds at the same
+    /// column/row. New cells are empty and cells beyond the new size are dropped.
+    pub fn resize(&mut self, rows: usize, columns: usize) -> &mut Self {
+        let mut resized = Canvas::new(rows, columns);
+        for (row, column, status) in self.iter_cells() {
+            if let Some(index) = resized.position_to_index(column, row) {
+                resized.put(index, status);
+            }
+        }
+
+        resized.generation = self.generation + 1;
+        *self = resized;
+        self
     }
 
     /// Remove all pieces from the canvas.
     pub fn clear_all(&mut self) -> &mut Self {
-        self.contents.fill(PointStatus::Empty);
+        self.fill(PointStatus::Empty);
+        self.generation += 1;
         self
     }
 
+    fn fill(&mut self, status: PointStatus) {
+        for index in 0..self.cells.len() {
+            self.put(index, status);
+        }
+    }
+
+    /// Overwrite the cell at `index`, keeping the bitsets in step.
+    fn put(&mut self, index: usize, status: PointStatus) {
+        self.filled
+            .assign(index, !matches!(status, PointStatus::Empty));
+        self.marked
+            .assign(index, matches!(status, PointStatus::MarkedForRemoval(_)));
+        self.cells[index] = match status {
+            PointStatus::Occupied(id) => id,
+            PointStatus::MarkedForRemoval(dwell) => dwell,
+            PointStatus::Empty => 0,
+        };
+    }
+
+    fn swap_cells(&mut self, a: usize, b: usize) {
+        let (status_a, status_b) = (self.status(a), self.status(b));
+        self.put(a, status_b);
+        self.put(b, status_a);
+    }
+
+    /// Returns true if the cell at `index` holds a block, not counting cells marked for removal.
+    fn is_occupied(&self, index: usize) -> bool {
+        self.filled.get(index) && !self.marked.get(index)
+    }
+
+    /// Status of the cell at `row`, `column`, or `None` if it is out of bounds.
+    pub fn get(&self, row: i32, column: i32) -> Option<PointStatus> {
+        self.position_to_index(column, row).map(|i| self.status(i))
+    }
+
+    /// Overwrite the status of the cell at `row`, `column`.
+    ///
+    /// Returns `false` without modifying the canvas if the cell is out of bounds.
+    pub fn set(&mut self, row: i32, column: i32, status: PointStatus) -> bool {
+        let Some(index) = self.position_to_index(column, row) else {
+            return false;
+        };
+
+        self.put(index, status);
+        self.generation += 1;
+        true
+    }
+
+    /// Exchange the statuses of the cells at `a` and `b`, each given as `(row, column)`.
+    ///
+    /// Returns `false` without modifying the canvas if either cell is out of bounds.
+    pub fn swap(&mut self, a: (i32, i32), b: (i32, i32)) -> bool {
+        let (Some(a), Some(b)) = (
+            self.position_to_index(a.1, a.0),
+            self.position_to_index(b.1, b.0),
+        ) else {
+            return false;
+        };
+
+        self.swap_cells(a, b);
+        self.generation += 1;
+        true
+    }
+
     /// Translate from row/col domain to 1d-array with stride domain.
     ///
     /// Returns `None` for invalid positions.
@@ -62,12 +622,12 @@ impl Canvas {
     /// Returns true if `block`'s coordinates would fit if the origin of the block was placed at
     /// the specified row/column.
     pub fn can_fit_at(&self, block: &Block, row: i32, column: i32) -> bool {
-        for p in block.coordinates() {
-            let Some(index) = self.position_to_index(column + p.x, row + p.y) else {
+        for p in block.at(row, column) {
+            let Some(index) = self.position_to_index(p.x, p.y) else {
                 return false;
             };
 
-            if let PointStatus::Occupied = self.contents[index] {
+            if self.is_occupied(index) {
                 return false;
             }
         }
@@ -76,14 +636,98 @@ impl Canvas {
     }
 
     pub fn can_fit(&self, block: &Block) -> Option<PlayableBlock> {
+        let (row, column) = match self.can_fit_by_masks(block) {
+            Some(origin) => origin?,
+            None => self.can_fit_by_scan(block)?,
+        };
+
+        Some(PlayableBlock {
+            block: block.clone(),
+            row,
+            column,
+        })
+    }
+
+    /// Try `block` in each of its four rotations, turning left each time, and return the first
+    /// rotation which fits somewhere on the board.
+    pub fn can_fit_any_rotation(&self, block: &Block) -> Option<Block> {
+        self.can_fit_in_any_rotation(block)
+            .map(|playable| playable.block)
+    }
+
+    /// Same as [`Canvas::can_fit_any_rotation`], along with where the rotated block fits.
+    pub(crate) fn can_fit_in_any_rotation(&self, block: &Block) -> Option<PlayableBlock> {
+        let mut rotated = block.clone();
+        for _ in 0..4 {
+            if let Some(playable) = self.can_fit(&rotated) {
+                return Some(playable);
+            }
+            rotated.rotate_left();
+        }
+
+        None
+    }
+
+    /// [`Canvas::can_fit`] using a bitmask per row, so that each candidate origin costs one AND
+    /// per row of the block. Returns `None` if the board is too wide for the masks or the block is
+    /// empty, otherwise the first fitting `(row, column)`, if any.
+    fn can_fit_by_masks(&self, block: &Block) -> Option<Option<(i32, i32)>> {
+        if self.columns > 64 {
+            return None;
+        }
+
+        let coords = block.coordinates();
+        if coords.is_empty() {
+            return None;
+        }
+
+        let (min, max) = block.bounding_box();
+        // Blocks larger than the board never fit, and would not fit in a mask either.
+        if i64::from(max.x) - i64::from(min.x) >= self.columns as i64
+            || i64::from(max.y) - i64::from(min.y) >= self.rows as i64
+        {
+            return Some(None);
+        }
+
+        let mut block_rows = vec![0u64; (max.y - min.y + 1) as usize];
+        for p in coords {
+            block_rows[(p.y - min.y) as usize] |= 1 << (p.x - min.x);
+        }
+
+        let board_rows: Vec<u64> = (0..self.rows)
+            .map(|row| {
+                (0..self.columns)
+                    .filter(|&col| self.is_occupied(self.columns * row + col))
+                    .fold(0, |mask, col| mask | 1 << col)
+            })
+            .collect();
+
+        // Only origins which keep the whole block on the board can fit.
+        let columns = 0.max(-min.x)..self.columns as i32 - max.x;
+        let rows = 0.max(-min.y)..self.rows as i32 - max.y;
+        for column in columns {
+            for row in rows.clone() {
+                let bottom = (row + min.y) as usize;
+                let shift = column + min.x;
+                let fits = block_rows
+                    .iter()
+                    .zip(&board_rows[bottom..])
+                    .all(|(block_row, board_row)| block_row << shift & board_row == 0);
+                if fits {
+                    return Some(Some((row, column)));
+                }
+            }
+        }
+
+        Some(None)
+    }
+
+    /// [`Canvas::can_fit`] checking every origin with [`Canvas::can_fit_at`].
+    fn can_fit_by_scan(&self, block: &Block) -> Option<(i32, i32)> {
         for column in 0..self.columns {
             for row in 0..self.rows {
-                if self.can_fit_at(&block, row as i32, column as i32) {
-                    return Some(PlayableBlock {
-                        block: block.clone(),
-                        row: row as i32,
-                        column: column as i32,
-                    });
+                if self.can_fit_at(block, row as i32, column as i32) {
+                    return Some((row as i32, column as i32));
                 }
             }
         }
@@ -91,117 +735,389 @@ impl Canvas {
         None
     }
 
+    /// Every `(row, column)` origin at which `block` fits in its current orientation, in the same
+    /// order [`Canvas::can_fit`] searches them.
+    pub fn find_placements(&self, block: &Block) -> Vec<(i32, i32)> {
+        let mut placements = Vec::new();
+        for column in 0..self.columns as i32 {
+            for row in 0..self.rows as i32 {
+                if self.can_fit_at(block, row, column) {
+                    placements.push((row, column));
+                }
+            }
+        }
+
+        placements
+    }
+
+    /// Find the legal origin for `block` closest to the desired row/column, so that a slightly
+    /// misplaced drop still lands somewhere sensible. Returns `(row, column)`.
+    ///
+    /// Returns `None` if the block does not fit anywhere.
+    pub fn nearest_fitting_origin(
+        &self,
+        block: &Block,
+        desired_row: i32,
+        desired_column: i32,
+    ) -> Option<(i32, i32)> {
+        let coords = block.coordinates();
+        let min_x = coords.iter().map(|p| p.x).min()?;
+        let max_x = coords.iter().map(|p| p.x).max()?;
+        let min_y = coords.iter().map(|p| p.y).min()?;
+        let max_y = coords.iter().map(|p| p.y).max()?;
+
+        // Only consider origins which keep the whole block on the board.
+        let rows = -min_y..self.rows as i32 - max_y;
+        let columns = -min_x..self.columns as i32 - max_x;
+
+        rows.flat_map(|row| columns.clone().map(move |column| (row, column)))
+            .filter(|&(row, column)| self.can_fit_at(block, row, column))
+            .min_by_key(|&(row, column)| {
+                (row - desired_row).pow(2) + (column - desired_column).pow(2)
+            })
+    }
+
     /// Returns None if the block is not playable.
     pub fn try_make_playable(&self, block: &Block, row: i32, column: i32) -> Option<PlayableBlock> {
-        if !self.can_fit_at(block, row, column) {
-            return None;
+        self.make_playable(block, row, column).ok()
+    }
+
+    /// Same as [`Canvas::try_make_playable`], explaining why the block cannot be placed. A block
+    /// which is both off the board and over occupied cells is reported as
+    /// [`PlaceError::OutOfBounds`].
+    pub fn make_playable(
+        &self,
+        block: &Block,
+        row: i32,
+        column: i32,
+    ) -> Result<PlayableBlock, PlaceError> {
+        let mut overlaps = false;
+        for p in block.at(row, column) {
+            let Some(index) = self.position_to_index(p.x, p.y) else {
+                return Err(PlaceError::OutOfBounds);
+            };
+            overlaps |= self.is_occupied(index);
         }
 
-        Some(PlayableBlock {
+        if overlaps {
+            return Err(PlaceError::Overlap);
+        }
+
+        Ok(PlayableBlock {
             block: block.clone(),
             row,
             column,
         })
     }
 
+    /// Add `block` with its origin at `row`/`column`, then clear any lines it completes. Returns
+    /// the number of lines cleared.
+    pub fn place(&mut self, block: &Block, row: i32, column: i32) -> Result<usize, PlaceError> {
+        let playable = self.make_playable(block, row, column)?;
+
+        self.add(&playable);
+        Ok(self.clear_completed_lines().total())
+    }
+
     /// Add `block` to the canvas.
     pub fn add(&mut self, block: &PlayableBlock) -> &mut Self {
-        for p in block.block.coordinates() {
-            if let Some(index) = self.position_to_index(block.column + p.x, block.row + p.y) {
-                self.contents[index] = PointStatus::Occupied;
+        for p in block.block.at(block.row, block.column) {
+            if let Some(index) = self.position_to_index(p.x, p.y) {
+                self.put(index, PointStatus::Occupied(block.color()));
             }
         }
 
+        self.generation += 1;
         self
     }
 
-    /// Clear all completed rows and columns then returns number of rows and columns removed.
-    pub fn clear_completed_lines(&mut self) -> usize {
-        let mut removed = 0;
-
-        // mark cols
-        for col in 0..self.columns {
-            if let Some(true) = self.is_complete_column(col) {
-                for row in 0..self.rows {
-                    if let Some(index) = self.position_to_index(col as i32, row as i32) {
-                        self.contents[index] = PointStatus::MarkedForRemoval;
-                    }
-                }
-                removed += 1;
+    /// Erase the cells covered by `block`, e.g. to undo placing it. Neighboring cells are left
+    /// untouched.
+    pub fn remove(&mut self, block: &PlayableBlock) -> &mut Self {
+        for p in block.block.at(block.row, block.column) {
+            if let Some(index) = self.position_to_index(p.x, p.y) {
+                self.put(index, PointStatus::Empty);
             }
         }
 
-        // mark rows
-        for row in 0..self.rows {
-            if let Some(true) = self.is_complete_row(row) {
-                for col in 0..self.columns {
-                    if let Some(index) = self.position_to_index(col as i32, row as i32) {
-                        self.contents[index] = PointStatus::MarkedForRemoval;
-                    }
-                }
-                removed += 1;
-            }
-        }
+        self.generation += 1;
+        self
+    }
 
-        // mark empty
-        for p in self.contents.iter_mut() {
-            if let PointStatus::MarkedForRemoval = *p {
-                *p = PointStatus::Empty;
-            }
+    /// Clear all completed rows and columns then returns the indices of the rows and columns
+    /// removed. Lines already marked for removal are cleared too.
+    pub fn clear_completed_lines(&mut self) -> ClearedLines {
+        let removed = self.complete_lines(true);
+        if removed.total() > 0 {
+            self.put_lines(&removed, PointStatus::Empty);
+            self.generation += 1;
         }
 
         removed
     }
 
-    /// Return `Some(true)` if the row is completely occupied.
-    pub fn is_complete_row(&self, row: usize) -> Option<bool> {
-        // Invalid row selection.
-        if self.rows <= row {
-            return None;
+    /// Same as [`Canvas::clear_completed_lines`], with the cleared row and column indices returned
+    /// as a `(rows, columns)` pair.
+    pub fn clear_completed_lines_detailed(&mut self) -> (Vec<usize>, Vec<usize>) {
+        let ClearedLines { rows, columns } = self.clear_completed_lines();
+        (rows, columns)
+    }
+
+    /// Clear completed lines and, if `gravity` is set, let the remaining cells fall and keep
+    /// clearing until no more lines complete. Returns every line cleared along the way, so the
+    /// same index may appear more than once.
+    pub fn resolve(&mut self, gravity: bool) -> ClearedLines {
+        let mut cleared = self.clear_completed_lines();
+        if !gravity {
+            return cleared;
         }
 
-        let mut sum = 0;
-        for col in 0..self.columns {
-            if let Some(index) = self.position_to_index(col as i32, row as i32) {
-                sum = match self.contents[index] {
-                    PointStatus::Occupied => sum + 1,
-                    PointStatus::MarkedForRemoval => sum + 1,
-                    PointStatus::Empty => sum,
-                };
+        loop {
+            self.apply_gravity();
+            let cascade = self.clear_completed_lines();
+            if cascade.total() == 0 {
+                break;
             }
-        }
 
-        if sum != self.columns {
-            return Some(false);
+            cleared.rows.extend(cascade.rows);
+            cleared.columns.extend(cascade.columns);
         }
 
-        Some(true)
+        cleared
     }
 
-    /// Return `Some(true)` if the column is completely occupied.
-    pub fn is_complete_column(&self, column: usize) -> Option<bool> {
-        // Invalid column selection.
-        if self.columns <= column {
-            return None;
+    /// Mark all completed rows and columns for removal without clearing them, returning how many
+    /// lines were marked. The marked cells are swept by the next [`Canvas::tick_marks`] or
+    /// [`Canvas::clear_completed_lines`].
+    pub fn mark_completed_lines(&mut self) -> usize {
+        self.mark_completed_lines_with_dwell(1).total()
+    }
+
+    /// Every cell currently marked for removal as `(row, column)`, in the same order as
+    /// [`Canvas::contents`].
+    pub fn marked_cells(&self) -> Vec<(i32, i32)> {
+        self.iter_cells()
+            .filter(|(_, _, status)| matches!(status, PointStatus::MarkedForRemoval(_)))
+            .map(|(row, column, _)| (row, column))
+            .collect()
+    }
+
+    /// Mark all completed rows and columns for removal without clearing them, returning the
+    /// indices of the rows and columns marked. The marked cells are swept after `dwell` calls to
+    /// [`Canvas::tick_marks`], which lets a UI hold the clear effect for a few frames. Cells
+    /// which are already marked keep their remaining dwell.
+    pub fn mark_completed_lines_with_dwell(&mut self, dwell: u8) -> ClearedLines {
+        let marked = self.complete_lines(false);
+        if marked.total() > 0 {
+            self.put_lines(&marked, PointStatus::MarkedForRemoval(dwell.max(1)));
+            self.generation += 1;
+        }
+
+        marked
+    }
+
+    /// Completed rows and columns, leaving out those already wholly marked for removal unless
+    /// `include_marked` is set.
+    fn complete_lines(&self, include_marked: bool) -> ClearedLines {
+        let columns = self.columns;
+        let rows = (0..self.rows)
+            .filter(|&row| {
+                self.is_complete_row(row) == Some(true)
+                    && (include_marked || !self.marked.all_in(columns * row, columns))
+            })
+            .collect();
+        let columns = (0..columns)
+            .filter(|&col| {
+                self.is_complete_column(col) == Some(true)
+                    && (include_marked
+                        || !(0..self.rows).all(|row| self.marked.get(columns * row + col)))
+            })
+            .collect();
+
+        ClearedLines { rows, columns }
+    }
+
+    /// Set every cell in `lines` to `status`. When marking, cells which are already marked for
+    /// removal keep their remaining dwell.
+    fn put_lines(&mut self, lines: &ClearedLines, status: PointStatus) {
+        let (rows, columns) = (self.rows, self.columns);
+        let cells: Vec<usize> = lines
+            .rows
+            .iter()
+            .flat_map(|&row| (0..columns).map(move |col| columns * row + col))
+            .chain(
+                lines
+                    .columns
+                    .iter()
+                    .flat_map(|&col| (0..rows).map(move |row| columns * row + col)),
+            )
+            .collect();
+
+        for index in cells {
+            if status == PointStatus::Empty || !self.marked.get(index) {
+                self.put(index, status);
+            }
         }
+    }
 
-        let mut sum = 0;
+    /// Count down every cell marked for removal, emptying those which reach zero. Returns the
+    /// number of cells swept.
+    pub fn tick_marks(&mut self) -> usize {
+        if self.marked.none() {
+            return 0;
+        }
 
-        for row in 0..self.rows {
-            if let Some(index) = self.position_to_index(column as i32, row as i32) {
-                sum = match self.contents[index] {
-                    PointStatus::Occupied => sum + 1,
-                    PointStatus::MarkedForRemoval => sum + 1,
-                    PointStatus::Empty => sum,
-                };
+        let mut swept = 0;
+        for index in 0..self.cells.len() {
+            if !self.marked.get(index) {
+                continue;
+            }
+            if self.cells[index] <= 1 {
+                self.put(index, PointStatus::Empty);
+                swept += 1;
+            } else {
+                self.cells[index] -= 1;
             }
         }
 
-        if sum != self.rows {
-            return Some(false);
+        self.generation += 1;
+        swept
+    }
+
+    /// Let every occupied cell fall as far down its column as it can, keeping the cells of each
+    /// column in their original order along with their block ids.
+    pub fn apply_gravity(&mut self) -> &mut Self {
+        let mut moved = false;
+
+        for col in 0..self.columns {
+            let mut floor = 0;
+            for row in 0..self.rows {
+                let index = self.columns * row + col;
+                if !self.filled.get(index) {
+                    continue;
+                }
+
+                if row != floor {
+                    self.swap_cells(index, self.columns * floor + col);
+                    moved = true;
+                }
+                floor += 1;
+            }
         }
 
-        Some(true)
+        if moved {
+            self.generation += 1;
+        }
+        self
+    }
+
+    /// Find the rows and columns that are currently complete without clearing them.
+    pub fn count_completed_lines(&self) -> ClearedLines {
+        ClearedLines {
+            rows: self.completed_rows().collect(),
+            columns: self.completed_columns().collect(),
+        }
+    }
+
+    /// Lazily yield the index of every completed row, bottom to top.
+    pub fn completed_rows(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.rows).filter(|&row| self.is_complete_row(row) == Some(true))
+    }
+
+    /// Lazily yield the index of every completed column, left to right.
+    pub fn completed_columns(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.columns).filter(|&col| self.is_complete_column(col) == Some(true))
+    }
+
+    /// Indices of every completed row, bottom to top.
+    pub fn check_rows(&self) -> Vec<usize> {
+        self.completed_rows().collect()
+    }
+
+    /// Indices of every completed column, left to right.
+    pub fn check_columns(&self) -> Vec<usize> {
+        self.completed_columns().collect()
+    }
+
+    /// Shift every cell up one row and insert a garbage row at the bottom which is occupied in
+    /// every column except those listed in `gaps`.
+    ///
+    /// Returns `false` without modifying the canvas if the shift would push occupied cells off
+    /// the top of the board.
+    pub fn push_garbage_row(&mut self, gaps: &[usize]) -> bool {
+        let Some(top) = self.rows.checked_sub(1) else {
+            return false;
+        };
+
+        if (self.columns * top..self.cells.len()).any(|index| self.filled.get(index)) {
+            return false;
+        }
+
+        // Rows are stored bottom first, so moving everything up a row moves each cell one stride
+        // further along. The top row is empty, so nothing is lost.
+        for index in (self.columns..self.cells.len()).rev() {
+            let below = self.status(index - self.columns);
+            self.put(index, below);
+        }
+        for col in 0..self.columns {
+            let status = if gaps.contains(&col) {
+                PointStatus::Empty
+            } else {
+                PointStatus::Occupied(0)
+            };
+            self.put(col, status);
+        }
+
+        self.generation += 1;
+        true
+    }
+
+    /// Returns true if placing `block` with its origin at the given row/column would complete
+    /// `target_row`. Returns false if the block does not fit there.
+    pub fn would_complete_row(
+        &self,
+        block: &Block,
+        row: i32,
+        column: i32,
+        target_row: usize,
+    ) -> bool {
+        if target_row >= self.rows || !self.can_fit_at(block, row, column) {
+            return false;
+        }
+
+        let covered: Vec<i32> = block
+            .at(row, column)
+            .into_iter()
+            .filter(|p| p.y == target_row as i32)
+            .map(|p| p.x)
+            .collect();
+
+        (0..self.columns).all(|col| {
+            covered.contains(&(col as i32))
+                || self
+                    .position_to_index(col as i32, target_row as i32)
+                    .is_some_and(|index| self.filled.get(index))
+        })
+    }
+
+    /// Return `Some(true)` if the row is completely occupied.
+    pub fn is_complete_row(&self, row: usize) -> Option<bool> {
+        // Invalid row selection.
+        if self.rows <= row {
+            return None;
+        }
+
+        Some(self.filled.all_in(self.columns * row, self.columns))
+    }
+
+    /// Return `Some(true)` if the column is completely occupied.
+    pub fn is_complete_column(&self, column: usize) -> Option<bool> {
+        // Invalid column selection.
+        if self.columns <= column {
+            return None;
+        }
+
+        Some((0..self.rows).all(|row| self.filled.get(self.columns * row + column)))
     }
 }
 
@@ -211,33 +1127,65 @@ impl Default for Canvas {
     }
 }
 
+impl PartialEq for Canvas {
+    /// Boards are equal when they have the same shape and contents, regardless of their
+    /// generation. A cell marked for removal never equals an occupied one.
+    fn eq(&self, other: &Self) -> bool {
+        // Empty cells always hold 0, so equal bytes and bitsets mean equal statuses.
+        self.rows == other.rows
+            && self.columns == other.columns
+            && self.cells == other.cells
+            && self.filled == other.filled
+            && self.marked == other.marked
+    }
+}
+
+impl Eq for Canvas {}
+
+impl Hash for Canvas {
+    /// Hashes the board's shape and which cells are filled, treating cells marked for removal as
+    /// occupied, so boards can be memoized in a `HashMap` or `HashSet`.
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.rows.hash(state);
+        self.columns.hash(state);
+        self.filled.words.hash(state);
+    }
+}
+
+impl fmt::Display for Canvas {
+    /// The board as players see it, top row first, without the labels `Debug` adds.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.to_char_grid().iter().rev() {
+            let line: Vec<String> = row.iter().map(char::to_string).collect();
+            writeln!(f, "{}", line.join(" "))?;
+        }
+
+        Ok(())
+    }
+}
+
 impl fmt::Debug for Canvas {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Row labels are right aligned to the width of the largest one.
+        let label_width = self.rows.saturating_sub(1).to_string().len();
+
         let mut canvas_char_view = Vec::new();
         for row in (0..self.rows).rev() {
-            canvas_char_view.push(char::from_digit(row as u32, 10).unwrap());
-            canvas_char_view.push(' ');
+            canvas_char_view.extend(format!("{row:>label_width$} ").chars());
             for col in 0..self.columns {
                 let content_index = self.position_to_index(col as i32, row as i32).unwrap();
-                let marker = match self.contents[content_index] {
-                    PointStatus::Occupied => '▅',
-                    PointStatus::MarkedForRemoval => '⏲',
-                    PointStatus::Empty => '.',
-                };
-                canvas_char_view.push(marker);
+                canvas_char_view.push(self.status(content_index).as_char());
                 canvas_char_view.push(' ');
             }
             canvas_char_view.push('\n');
         }
 
         // whitespace before x labels
-        for _ in 0..2 {
-            canvas_char_view.push(' ');
-        }
+        canvas_char_view.extend(std::iter::repeat_n(' ', label_width + 1));
 
-        // x labels
-        for c in "01234567".chars() {
-            canvas_char_view.push(c);
+        // x labels, wrapping past 9 so that each label stays one character wide
+        for col in 0..self.columns {
+            canvas_char_view.push(char::from_digit((col % 10) as u32, 10).unwrap());
             canvas_char_view.push(' ');
         }
         canvas_char_view.push('\n');
@@ -417,9 +1365,7 @@ mod tests {
     #[test]
     fn cant_fit_when_full() {
         let mut original = Canvas::new(8, 8);
-        for c in original.contents.iter_mut() {
-            *c = PointStatus::Occupied;
-        }
+        original.fill(PointStatus::Occupied(1));
 
         let all_blocks: [Block; 14] = [
             Block::rectangle(3, 3),
@@ -445,8 +1391,8 @@ mod tests {
     #[test]
     fn can_fit_when_barely_empty() {
         let mut original = Canvas::new(8, 8);
-        original.contents.fill(PointStatus::Occupied);
-        original.contents[63] = PointStatus::Empty;
+        original.fill(PointStatus::Occupied(1));
+        original.put(63, PointStatus::Empty);
 
         let wont_fit: [Block; 13] = [
             Block::rectangle(3, 3),
@@ -482,21 +1428,1294 @@ mod tests {
     #[test]
     fn can_clone() {
         let mut original = Canvas::new(3, 3);
-        original.contents[0] = PointStatus::Occupied;
-        original.contents[1] = PointStatus::Occupied;
-        original.contents[2] = PointStatus::Occupied;
+        original.put(0, PointStatus::Occupied(1));
+        original.put(1, PointStatus::Occupied(1));
+        original.put(2, PointStatus::Occupied(1));
 
         let duplicate = original.clone();
         for i in 0..3 {
-            if let PointStatus::Occupied = duplicate.contents[i] {
+            assert!(
+                matches!(duplicate.status(i), PointStatus::Occupied(_)),
+                "Expected contents to be cloned"
+            );
+        }
+
+        assert!(
+            matches!(duplicate.status(3), PointStatus::Empty),
+            "Expected contents to be cloned"
+        );
+    }
+
+    #[test]
+    fn can_push_garbage_row() {
+        let mut board = Canvas::new(4, 4);
+        let playable = board.try_make_playable(&Block::tee(), 0, 0).unwrap();
+        board.add(&playable);
+
+        assert!(board.push_garbage_row(&[2]));
+
+        // the tee moved up by one row
+        for (x, y) in [(0, 1), (1, 1), (2, 1), (1, 2)] {
+            let index = board.position_to_index(x, y).unwrap();
+            assert!(
+                matches!(board.status(index), PointStatus::Occupied(_)),
+                "Expected ({x}, {y}) to be occupied.\n{board:?}"
+            );
+        }
+        assert_eq!(
+            4 + 3,
+            board
+                .contents()
+                .iter()
+                .filter(|p| matches!(p, PointStatus::Occupied(_)))
+                .count()
+        );
+
+        // the new bottom row is full except for the gap
+        for x in 0..4 {
+            let index = board.position_to_index(x, 0).unwrap();
+            if x == 2 {
+                assert!(matches!(board.status(index), PointStatus::Empty));
             } else {
-                assert!(false, "Expected contents to be cloned");
+                assert!(matches!(board.status(index), PointStatus::Occupied(_)));
             }
         }
+    }
 
-        if let PointStatus::Empty = duplicate.contents[3] {
-        } else {
-            assert!(false, "Expected contents to be cloned");
+    #[test]
+    fn cant_push_garbage_row_off_the_top() {
+        let mut board = Canvas::new(4, 4);
+        let playable = board
+            .try_make_playable(&Block::rectangle(1, 1), 3, 1)
+            .unwrap();
+        board.add(&playable);
+
+        assert!(!board.push_garbage_row(&[0]));
+
+        // nothing moved
+        for (index, status) in board.contents().iter().enumerate() {
+            if index == 13 {
+                assert!(matches!(status, PointStatus::Occupied(_)));
+            } else {
+                assert!(matches!(status, PointStatus::Empty));
+            }
+        }
+    }
+
+    #[test]
+    fn can_count_completed_lines_without_clearing() {
+        let mut board = Canvas::new(8, 8);
+        let placements = [
+            (Block::line(5), 0, 0),
+            (Block::line(3), 0, 5),
+            (Block::rectangle(1, 3), 1, 0),
+            (Block::rectangle(1, 3), 4, 0),
+            (Block::rectangle(1, 1), 7, 0),
+        ];
+        for (block, row, column) in placements {
+            let playable = board.try_make_playable(&block, row, column).unwrap();
+            board.add(&playable);
+        }
+        let before = format!("{board:?}");
+
+        assert_eq!(
+            ClearedLines {
+                rows: vec![0],
+                columns: vec![0]
+            },
+            board.count_completed_lines()
+        );
+        assert_eq!(before, format!("{board:?}"));
+    }
+
+    #[test]
+    fn can_iterate_completed_lines() {
+        let mut board = Canvas::new(8, 8);
+        assert_eq!(0, board.completed_rows().count());
+        assert_eq!(0, board.completed_columns().count());
+
+        let placements = [
+            (Block::line(5), 2, 0),
+            (Block::line(3), 2, 5),
+            (Block::line(5), 6, 0),
+            (Block::line(3), 6, 5),
+            (Block::rectangle(1, 2), 0, 4),
+            (Block::rectangle(1, 3), 3, 4),
+            (Block::rectangle(1, 1), 7, 4),
+        ];
+        for (block, row, column) in placements {
+            let playable = board.try_make_playable(&block, row, column).unwrap();
+            board.add(&playable);
+        }
+
+        let rows: Vec<usize> = board.completed_rows().collect();
+        let columns: Vec<usize> = board.completed_columns().collect();
+        assert_eq!(vec![2, 6], rows);
+        assert_eq!(vec![4], columns);
+
+        for row in 0..board.rows {
+            assert_eq!(
+                board.is_complete_row(row),
+                Some(rows.contains(&row)),
+                "Row {row} disagrees with is_complete_row.\n{board:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn can_convert_to_char_grid() {
+        let mut board = Canvas::new(3, 4);
+        let playable = board.try_make_playable(&Block::tee(), 0, 1).unwrap();
+        board.add(&playable);
+
+        assert_eq!(
+            vec![
+                vec!['.', '▅', '▅', '▅'],
+                vec!['.', '.', '▅', '.'],
+                vec!['.', '.', '.', '.'],
+            ],
+            board.to_char_grid()
+        );
+    }
+
+    #[test]
+    fn generation_tracks_mutations() {
+        let mut board = Canvas::new(8, 8);
+        let start = board.generation();
+
+        let playable = board.try_make_playable(&Block::tee(), 0, 0).unwrap();
+        board.add(&playable);
+        let after_add = board.generation();
+        assert!(after_add > start);
+
+        // read-only calls and clears that remove nothing leave it untouched
+        board.can_fit(&Block::rectangle(3, 3));
+        board.count_completed_lines();
+        board.to_char_grid();
+        board.clear_completed_lines();
+        assert_eq!(after_add, board.generation());
+
+        board.clear_all();
+        assert!(board.generation() > after_add);
+    }
+
+    /// A 4x4 board with a full bottom row and cells floating above it which complete the bottom
+    /// row again once they fall.
+    fn cascading_board() -> Canvas {
+        let mut board = Canvas::new(4, 4);
+        for (x, y) in [
+            (0, 0),
+            (1, 0),
+            (2, 0),
+            (3, 0),
+            (3, 1),
+            (0, 2),
+            (1, 2),
+            (2, 2),
+        ] {
+            let index = board.position_to_index(x, y).unwrap();
+            board.put(index, PointStatus::Occupied(1));
+        }
+
+        board
+    }
+
+    #[test]
+    fn resolve_with_gravity_cascades() {
+        let mut board = cascading_board();
+
+        assert_eq!(
+            ClearedLines {
+                rows: vec![0, 0],
+                columns: vec![]
+            },
+            board.resolve(true)
+        );
+        assert!(
+            board
+                .contents()
+                .iter()
+                .all(|p| matches!(p, PointStatus::Empty))
+        );
+    }
+
+    #[test]
+    fn resolve_without_gravity_is_a_single_pass() {
+        let mut board = cascading_board();
+
+        assert_eq!(
+            ClearedLines {
+                rows: vec![0],
+                columns: vec![]
+            },
+            board.resolve(false)
+        );
+        assert_eq!(
+            vec![
+                vec!['.', '.', '.', '.'],
+                vec!['.', '.', '.', '▅'],
+                vec!['▅', '▅', '▅', '.'],
+                vec!['.', '.', '.', '.'],
+            ],
+            board.to_char_grid()
+        );
+    }
+
+    #[test]
+    fn square_matches_default() {
+        assert_eq!(Canvas::default(), Canvas::square(DEFAULT_CANVAS_WIDTH));
+        assert_eq!(Canvas::new(5, 5), Canvas::square(5));
+        assert_ne!(Canvas::new(5, 6), Canvas::square(5));
+    }
+
+    #[test]
+    fn can_snap_to_nearest_origin() {
+        let mut board = Canvas::new(8, 8);
+
+        // hanging off the right edge snaps back onto the board
+        assert_eq!(
+            Some((2, 5)),
+            board.nearest_fitting_origin(&Block::line(3), 2, 6)
+        );
+
+        // a legal drop stays put
+        assert_eq!(
+            Some((2, 1)),
+            board.nearest_fitting_origin(&Block::line(3), 2, 1)
+        );
+
+        // an occupied target moves to the closest free spot
+        for (block, column) in [(Block::rectangle(3, 3), 0), (Block::rectangle(2, 3), 3)] {
+            let playable = board.try_make_playable(&block, 3, column).unwrap();
+            board.add(&playable);
+        }
+        assert_eq!(
+            Some((4, 5)),
+            board.nearest_fitting_origin(&Block::line(3), 4, 4)
+        );
+
+        board.fill(PointStatus::Occupied(1));
+        assert_eq!(None, board.nearest_fitting_origin(&Block::line(3), 4, 4));
+    }
+
+    #[test]
+    fn marks_are_swept_after_their_dwell() {
+        let mut board = Canvas::new(4, 4);
+        let playable = board.try_make_playable(&Block::line(4), 0, 0).unwrap();
+        board.add(&playable);
+
+        assert_eq!(
+            ClearedLines {
+                rows: vec![0],
+                columns: vec![]
+            },
+            board.mark_completed_lines_with_dwell(2)
+        );
+
+        assert_eq!(0, board.tick_marks());
+        assert!(
+            board.contents()[0..4]
+                .iter()
+                .all(|p| matches!(p, PointStatus::MarkedForRemoval(1)))
+        );
+
+        assert_eq!(4, board.tick_marks());
+        assert_eq!(Canvas::new(4, 4), board);
+        assert_eq!(0, board.tick_marks());
+    }
+
+    #[test]
+    fn can_predict_row_completion() {
+        let mut board = Canvas::new(8, 8);
+        for row in [3, 4] {
+            let playable = board.try_make_playable(&Block::line(5), row, 0).unwrap();
+            board.add(&playable);
+            let playable = board.try_make_playable(&Block::line(2), row, 6).unwrap();
+            board.add(&playable);
+        }
+
+        let square = Block::rectangle(1, 1);
+        assert!(board.would_complete_row(&square, 3, 5, 3));
+        assert!(!board.would_complete_row(&square, 3, 5, 4));
+
+        // a vertical domino through column 5 completes both rows
+        let domino = Block::rectangle(1, 2);
+        assert!(board.would_complete_row(&domino, 3, 5, 3));
+        assert!(board.would_complete_row(&domino, 3, 5, 4));
+
+        // placements which don't fit complete nothing
+        assert!(!board.would_complete_row(&square, 3, 4, 3));
+
+        // the board is untouched
+        assert_eq!(Some(false), board.is_complete_row(3));
+    }
+
+    #[test]
+    fn can_swap_cells() {
+        let mut board = Canvas::new(4, 4);
+        let playable = board
+            .try_make_playable(&Block::rectangle(1, 1), 0, 0)
+            .unwrap();
+        board.add(&playable);
+
+        assert!(board.swap((0, 0), (2, 3)));
+        assert_eq!(PointStatus::Empty, board.status(0));
+        assert_eq!(PointStatus::Occupied(1), board.status(11));
+
+        let before = board.clone();
+        assert!(!board.swap((2, 3), (0, 4)));
+        assert!(!board.swap((0, -1), (2, 3)));
+        assert_eq!(before, board);
+    }
+
+    #[test]
+    fn clearing_reports_line_indices() {
+        let mut board = Canvas::new(8, 8);
+        for row in [0, 7] {
+            for (block, column) in [(Block::line(5), 0), (Block::line(3), 5)] {
+                let playable = board.try_make_playable(&block, row, column).unwrap();
+                board.add(&playable);
+            }
+        }
+        for row in [1, 4] {
+            let playable = board
+                .try_make_playable(&Block::rectangle(1, 3), row, 3)
+                .unwrap();
+            board.add(&playable);
+        }
+
+        assert_eq!(
+            ClearedLines {
+                rows: vec![0, 7],
+                columns: vec![3]
+            },
+            board.clear_completed_lines()
+        );
+        assert_eq!(Canvas::new(8, 8), board);
+    }
+
+    #[test]
+    fn can_convert_to_f32_grid() {
+        let mut board = Canvas::new(2, 3);
+        let playable = board.try_make_playable(&Block::line(2), 0, 1).unwrap();
+        board.add(&playable);
+        board.put(3, PointStatus::MarkedForRemoval(1));
+
+        let grid = board.as_f32_grid();
+        assert_eq!(board.rows * board.columns, grid.len());
+        assert_eq!(vec![0.0, 1.0, 1.0, 1.0, 0.0, 0.0], grid);
+    }
+
+    #[test]
+    fn can_find_reverted_board_in_set() {
+        let mut board = Canvas::new(8, 8);
+        let playable = board.try_make_playable(&Block::tee(), 2, 2).unwrap();
+        board.add(&playable);
+
+        let mut seen = std::collections::HashSet::new();
+        seen.insert(board.clone());
+
+        board.swap((2, 2), (7, 7));
+        assert!(!seen.contains(&board));
+
+        board.swap((2, 2), (7, 7));
+        assert!(seen.contains(&board));
+    }
+
+    #[test]
+    fn playable_block_keeps_color() {
+        let canvas = Canvas::default();
+        let playable = canvas.can_fit(&Block::tee().with_color(11)).unwrap();
+        assert_eq!(11, playable.color());
+    }
+
+    #[test]
+    fn clone_is_independent_of_original() {
+        let mut original = Canvas::new(8, 8);
+        let tee = original.try_make_playable(&Block::tee(), 0, 0).unwrap();
+        original.add(&tee);
+        let line = original.try_make_playable(&Block::line(3), 4, 2).unwrap();
+        original.add(&line);
+
+        let snapshot = original.clone();
+        assert_eq!(snapshot, original);
+
+        let square = original
+            .try_make_playable(&Block::rectangle(2, 2), 6, 6)
+            .unwrap();
+        original.add(&square.clone());
+
+        assert_ne!(snapshot, original);
+        assert!(snapshot.can_fit_at(&Block::rectangle(2, 2), 6, 6));
+        assert!(!original.can_fit_at(&Block::rectangle(2, 2), 6, 6));
+        assert!(!snapshot.can_fit_at(&Block::tee(), 0, 0));
+    }
+
+    #[test]
+    fn can_get_and_set_cells() {
+        let mut board = Canvas::new(4, 3);
+        assert_eq!(Some(PointStatus::Empty), board.get(3, 2));
+
+        let generation = board.generation();
+        assert!(board.set(3, 2, PointStatus::Occupied(1)));
+        assert_eq!(Some(PointStatus::Occupied(1)), board.get(3, 2));
+        assert_eq!(PointStatus::Occupied(1), board.contents()[3 * 3 + 2]);
+        assert!(board.generation() > generation);
+
+        assert!(board.set(0, 0, PointStatus::MarkedForRemoval(2)));
+        assert_eq!(Some(PointStatus::MarkedForRemoval(2)), board.get(0, 0));
+    }
+
+    #[test]
+    fn cant_get_or_set_out_of_bounds() {
+        let mut board = Canvas::new(4, 3);
+        let generation = board.generation();
+
+        assert_eq!(None, board.get(0, 3));
+        assert_eq!(None, board.get(4, 0));
+        assert_eq!(None, board.get(0, -1));
+        assert!(!board.set(0, 3, PointStatus::Occupied(1)));
+        assert!(!board.set(-1, 0, PointStatus::Occupied(1)));
+        assert_eq!(generation, board.generation());
+        assert_eq!(Canvas::new(4, 3), board);
+    }
+
+    #[test]
+    fn can_measure_occupancy() {
+        let mut board = Canvas::new(4, 4);
+        assert_eq!(0, board.count_occupied());
+        assert_eq!(0.0, board.occupancy());
+
+        let bottom = board
+            .try_make_playable(&Block::rectangle(3, 2), 0, 0)
+            .unwrap();
+        board.add(&bottom);
+        board.set(0, 3, PointStatus::Occupied(1));
+        board.set(1, 3, PointStatus::MarkedForRemoval(1));
+        assert_eq!(8, board.count_occupied());
+        assert_eq!(0.5, board.occupancy());
+
+        board.fill(PointStatus::Occupied(1));
+        assert_eq!(16, board.count_occupied());
+        assert_eq!(1.0, board.occupancy());
+    }
+
+    #[test]
+    fn can_tell_full_from_empty() {
+        let mut board = Canvas::new(3, 5);
+        assert!(board.is_empty());
+        assert!(!board.is_full());
+
+        let left = board
+            .try_make_playable(&Block::rectangle(3, 3), 0, 0)
+            .unwrap();
+        board.add(&left);
+        assert!(!board.is_empty());
+        assert!(!board.is_full());
+
+        let right = board
+            .try_make_playable(&Block::rectangle(2, 3), 0, 3)
+            .unwrap();
+        board.add(&right);
+        assert!(board.is_full());
+
+        board.clear_all();
+        assert!(board.is_empty());
+    }
+
+    #[test]
+    fn can_remove_placed_block() {
+        let mut board = Canvas::default();
+        let tee = board.try_make_playable(&Block::tee(), 3, 2).unwrap();
+        board.add(&tee);
+        board.remove(&tee);
+        assert!(board.is_empty());
+
+        let square = board
+            .try_make_playable(&Block::rectangle(2, 2), 0, 0)
+            .unwrap();
+        board.add(&square);
+        let line = board.try_make_playable(&Block::line(3), 2, 0).unwrap();
+        board.add(&line);
+        board.remove(&line);
+        assert_eq!(4, board.count_occupied());
+        assert!(!board.can_fit_at(&Block::rectangle(2, 2), 0, 0));
+    }
+
+    #[test]
+    fn detailed_clear_reports_row_and_column() {
+        let mut board = Canvas::new(4, 4);
+        let placements = [
+            (Block::line(4), 2, 0),
+            (Block::line_vertical(2), 0, 1),
+            (Block::rectangle(1, 1), 3, 1),
+        ];
+        for (block, row, column) in placements {
+            let playable = board.try_make_playable(&block, row, column).unwrap();
+            board.add(&playable);
         }
+
+        assert_eq!((vec![2], vec![1]), board.clear_completed_lines_detailed());
+        assert!(board.is_empty());
+    }
+
+    #[test]
+    fn can_find_every_placement() {
+        let board = Canvas::new(8, 8);
+        assert_eq!(64, board.find_placements(&Block::rectangle(1, 1)).len());
+        assert_eq!(32, board.find_placements(&Block::line(5)).len());
+
+        // Lines are at most five long, so use a board that a line spans exactly.
+        let narrow = Canvas::new(8, 5);
+        let placements = narrow.find_placements(&Block::line(5));
+        assert_eq!(8, placements.len());
+        assert!(placements.iter().all(|&(_, column)| column == 0));
+    }
+
+    #[test]
+    fn placements_agree_with_can_fit() {
+        let mut board = Canvas::new(4, 4);
+        let square = board
+            .try_make_playable(&Block::rectangle(2, 2), 0, 0)
+            .unwrap();
+        board.add(&square);
+
+        let tee = Block::tee();
+        let placements = board.find_placements(&tee);
+        let first = board.can_fit(&tee).unwrap();
+        assert_eq!((first.row, first.column), placements[0]);
+        assert!(
+            placements
+                .iter()
+                .all(|&(row, column)| board.can_fit_at(&tee, row, column))
+        );
+    }
+
+    #[test]
+    fn can_iterate_cells_with_coordinates() {
+        let mut board = Canvas::new(4, 5);
+        let tee = board.try_make_playable(&Block::tee(), 1, 2).unwrap();
+        board.add(&tee);
+
+        let mut occupied: Vec<(i32, i32)> = board
+            .iter_cells()
+            .filter(|(_, _, status)| matches!(status, PointStatus::Occupied(_)))
+            .map(|(row, column, _)| (row, column))
+            .collect();
+        occupied.sort();
+
+        assert_eq!(vec![(1, 2), (1, 3), (1, 4), (2, 3)], occupied);
+        assert_eq!(20, board.iter_cells().count());
+        assert_eq!(
+            Some((3, 4)),
+            board
+                .iter_cells()
+                .last()
+                .map(|(row, column, _)| (row, column))
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip_preserves_canvas() {
+        let mut board = Canvas::new(4, 5);
+        let tee = board.try_make_playable(&Block::tee(), 1, 2).unwrap();
+        board.add(&tee);
+        board.set(0, 0, PointStatus::MarkedForRemoval(2));
+
+        let json = serde_json::to_string(&board).unwrap();
+        let restored: Canvas = serde_json::from_str(&json).unwrap();
+        assert_eq!(board, restored);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_rejects_mismatched_contents() {
+        let json = r#"{"columns":2,"rows":2,"contents":["Empty",{"Occupied":3},"Empty"]}"#;
+        assert!(serde_json::from_str::<Canvas>(json).is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_rejects_oversized_dimensions() {
+        for (rows, columns) in [(2, usize::MAX / 2 + 1), (0, usize::MAX)] {
+            let json = format!(r#"{{"columns":{columns},"rows":{rows},"contents":[]}}"#);
+            assert!(serde_json::from_str::<Canvas>(&json).is_err(), "{json}");
+        }
+    }
+
+    #[test]
+    fn can_parse_grid() {
+        let board = Canvas::from_str_grid(
+            "
+            . . . #
+            # # # #
+            # . # .
+            ",
+        )
+        .unwrap();
+
+        assert_eq!(3, board.rows);
+        assert_eq!(4, board.columns);
+        assert_eq!(Some(PointStatus::Occupied(0)), board.get(2, 3));
+        assert_eq!(Some(PointStatus::Empty), board.get(0, 1));
+        assert_eq!(Some(false), board.is_complete_row(0));
+        assert_eq!(Some(true), board.is_complete_row(1));
+        assert_eq!(Some(false), board.is_complete_row(2));
+        assert_eq!(vec![1], board.completed_rows().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn can_reparse_debug_output() {
+        let mut board = Canvas::new(8, 8);
+        let tee = board.try_make_playable(&Block::tee(), 2, 3).unwrap();
+        board.add(&tee);
+        let line = board.try_make_playable(&Block::line(5), 7, 0).unwrap();
+        board.add(&line);
+
+        // Block ids are not printed, so only the layout survives.
+        let parsed = Canvas::from_str_grid(&format!("{board:?}")).unwrap();
+        assert_eq!(board.to_char_grid(), parsed.to_char_grid());
+    }
+
+    #[test]
+    fn cant_parse_malformed_grid() {
+        assert_eq!(
+            Err(ParseCanvasError::RaggedRow {
+                row: 1,
+                expected: 3,
+                found: 2
+            }),
+            Canvas::from_str_grid("...\n##")
+        );
+        assert_eq!(
+            Err(ParseCanvasError::UnexpectedChar('x')),
+            Canvas::from_str_grid("..x")
+        );
+        assert_eq!(
+            Err(ParseCanvasError::Empty),
+            Canvas::from_str_grid("\n  \n")
+        );
+    }
+
+    #[test]
+    fn parsing_only_skips_the_debug_labels() {
+        // a mistyped row is not mistaken for labels
+        assert_eq!(
+            Err(ParseCanvasError::UnexpectedChar('a')),
+            Canvas::from_str_grid("...\nab12")
+        );
+        assert_eq!(
+            Err(ParseCanvasError::UnexpectedChar('1')),
+            Canvas::from_str_grid("12\n..")
+        );
+        assert_eq!(
+            Err(ParseCanvasError::UnexpectedChar('2')),
+            Canvas::from_str_grid(". .\n0 2")
+        );
+
+        let board = Canvas::from_str_grid("1 # .\n0 . #\n  0 1").unwrap();
+        assert_eq!((2, 2), (board.rows, board.columns));
+        assert_eq!(Some(PointStatus::Occupied(0)), board.get(1, 0));
+    }
+
+    #[test]
+    fn can_grow_board() {
+        let mut board = Canvas::from_str_grid(
+            "
+            # . .
+            . # .
+            ",
+        )
+        .unwrap();
+
+        board.resize(3, 4);
+        assert_eq!(
+            Canvas::from_str_grid(
+                "
+                . . . .
+                # . . .
+                . # . .
+                ",
+            )
+            .unwrap(),
+            board
+        );
+    }
+
+    #[test]
+    fn can_shrink_board() {
+        let mut board = Canvas::from_str_grid(
+            "
+            # . # #
+            . # . #
+            # # . .
+            ",
+        )
+        .unwrap();
+
+        board.resize(2, 2);
+        assert_eq!(
+            Canvas::from_str_grid(
+                "
+                . #
+                # #
+                ",
+            )
+            .unwrap(),
+            board
+        );
+        assert_eq!(4, board.contents().len());
+    }
+
+    #[test]
+    fn placed_cells_keep_block_id() {
+        let mut board = Canvas::new(4, 4);
+        let tee = board.try_make_playable(&Block::tee(), 0, 0).unwrap();
+        board.add(&tee);
+        let dot = board
+            .try_make_playable(&Block::rectangle(1, 1).with_color(9), 3, 3)
+            .unwrap();
+        board.add(&dot);
+
+        let tee_id = Block::tee().color();
+        assert_eq!(Some(PointStatus::Occupied(tee_id)), board.get(0, 0));
+        assert_eq!(Some(PointStatus::Occupied(tee_id)), board.get(1, 1));
+        assert_eq!(Some(PointStatus::Occupied(9)), board.get(3, 3));
+        assert_eq!(Some(PointStatus::Empty), board.get(1, 0));
+    }
+
+    #[test]
+    fn lines_of_mixed_ids_still_clear() {
+        let mut board = Canvas::new(4, 4);
+        for (block, column) in [(Block::line(2), 0), (Block::rectangle(2, 1), 2)] {
+            let playable = board.try_make_playable(&block, 0, column).unwrap();
+            board.add(&playable);
+        }
+
+        assert_eq!(Some(true), board.is_complete_row(0));
+        assert_eq!(vec![0], board.clear_completed_lines().rows);
+        assert!(board.is_empty());
+    }
+
+    #[test]
+    fn debug_labels_every_column() {
+        let rendered = format!("{:?}", Canvas::new(4, 10));
+        let labels: Vec<&str> = rendered
+            .lines()
+            .last()
+            .unwrap()
+            .split_whitespace()
+            .collect();
+        assert_eq!(
+            vec!["0", "1", "2", "3", "4", "5", "6", "7", "8", "9"],
+            labels
+        );
+
+        let rendered = format!("{:?}", Canvas::new(2, 12));
+        let labels = rendered.lines().last().unwrap().split_whitespace().count();
+        assert_eq!(12, labels);
+        assert!(rendered.lines().last().unwrap().ends_with("0 1 "));
+    }
+
+    #[test]
+    fn debug_renders_tall_boards() {
+        let mut board = Canvas::square(12);
+        board.set(11, 0, PointStatus::Occupied(1));
+        let rendered = format!("{board:?}");
+        let lines: Vec<&str> = rendered.lines().collect();
+
+        assert_eq!(13, lines.len());
+        assert!(lines[0].starts_with("11 ▅ . "));
+        assert!(lines[2].starts_with(" 9 . "));
+        assert!(lines[11].starts_with(" 0 . "));
+        assert!(lines[12].starts_with("   0 1 "));
+        assert_eq!(lines[0].chars().count(), lines[12].chars().count());
+
+        let parsed = Canvas::from_str_grid(&rendered).unwrap();
+        assert_eq!(board.to_char_grid(), parsed.to_char_grid());
+    }
+
+    #[test]
+    fn placement_lands_at_row_and_column() {
+        let mut board = Canvas::new(6, 8);
+        // Long edge along x, short edge up the y axis.
+        let elle = Block::elle(2, 3);
+        let playable = board.try_make_playable(&elle, 2, 5).unwrap();
+        board.add(&playable);
+
+        let mut occupied: Vec<(i32, i32)> = board
+            .iter_cells()
+            .filter(|(_, _, status)| matches!(status, PointStatus::Occupied(_)))
+            .map(|(row, column, _)| (row, column))
+            .collect();
+        occupied.sort();
+        assert_eq!(vec![(2, 5), (2, 6), (2, 7), (3, 5)], occupied);
+
+        assert_eq!(Some(PointStatus::Occupied(elle.color())), board.get(2, 5));
+        assert_eq!(Some(PointStatus::Empty), board.get(5, 2));
+
+        // Placements are reported in the same row, column order they are made in.
+        let placements = board.find_placements(&elle);
+        assert!(!placements.contains(&(2, 5)));
+        assert!(placements.contains(&(0, 5)));
+    }
+
+    /// Boards of varying shapes with a random mix of empty, occupied and marked cells, some with
+    /// completed lines.
+    fn random_boards() -> Vec<Canvas> {
+        use rand::{Rng, SeedableRng, rngs::StdRng};
+
+        let mut rng = StdRng::seed_from_u64(551);
+        let mut boards = Vec::new();
+        for (rows, columns) in [(8, 8), (5, 13), (9, 9), (3, 70)] {
+            for _ in 0..25 {
+                let density: f64 = rng.random_range(0.3..1.0);
+                let contents = (0..rows * columns)
+                    .map(|_| {
+                        if !rng.random_bool(density) {
+                            PointStatus::Empty
+                        } else if rng.random_bool(0.1) {
+                            PointStatus::MarkedForRemoval(rng.random_range(1..3))
+                        } else {
+                            PointStatus::Occupied(rng.random_range(0..5))
+                        }
+                    })
+                    .collect();
+                let mut board = Canvas::from_contents(rows, columns, contents);
+                let row = rng.random_range(0..rows);
+                for col in 0..columns {
+                    board.put(columns * row + col, PointStatus::Occupied(1));
+                }
+                boards.push(board);
+            }
+        }
+
+        boards
+    }
+
+    fn naive_is_filled(board: &Canvas, x: usize, y: usize) -> bool {
+        !matches!(board.status(board.columns * y + x), PointStatus::Empty)
+    }
+
+    fn naive_can_fit_at(board: &Canvas, block: &Block, row: i32, column: i32) -> bool {
+        block.at(row, column).into_iter().all(|p| {
+            p.x >= 0
+                && p.y >= 0
+                && (p.x as usize) < board.columns
+                && (p.y as usize) < board.rows
+                && !matches!(
+                    board.status(board.columns * p.y as usize + p.x as usize),
+                    PointStatus::Occupied(_)
+                )
+        })
+    }
+
+    #[test]
+    fn bitset_matches_cell_scan_on_random_boards() {
+        let blocks = Block::catalog_all_orientations();
+        for board in random_boards() {
+            for row in 0..board.rows {
+                assert_eq!(
+                    (0..board.columns).all(|x| naive_is_filled(&board, x, row)),
+                    board.is_complete_row(row).unwrap()
+                );
+            }
+            for col in 0..board.columns {
+                assert_eq!(
+                    (0..board.rows).all(|y| naive_is_filled(&board, col, y)),
+                    board.is_complete_column(col).unwrap()
+                );
+            }
+            assert_eq!(
+                board
+                    .contents()
+                    .iter()
+                    .filter(|p| !matches!(p, PointStatus::Empty))
+                    .count(),
+                board.count_occupied()
+            );
+
+            for block in &blocks {
+                for row in -2..board.rows as i32 + 2 {
+                    for column in -2..board.columns as i32 + 2 {
+                        assert_eq!(
+                            naive_can_fit_at(&board, block, row, column),
+                            board.can_fit_at(block, row, column),
+                            "{block} at {row}, {column} on\n{board:?}"
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn bitset_clears_like_cell_scan_on_random_boards() {
+        for mut board in random_boards() {
+            let rows: Vec<usize> = (0..board.rows)
+                .filter(|&y| (0..board.columns).all(|x| naive_is_filled(&board, x, y)))
+                .collect();
+            let columns: Vec<usize> = (0..board.columns)
+                .filter(|&x| (0..board.rows).all(|y| naive_is_filled(&board, x, y)))
+                .collect();
+            let expected: Vec<PointStatus> = board
+                .iter_cells()
+                .map(|(row, column, status)| {
+                    if rows.contains(&(row as usize)) || columns.contains(&(column as usize)) {
+                        PointStatus::Empty
+                    } else {
+                        status
+                    }
+                })
+                .collect();
+
+            assert_eq!(
+                ClearedLines { rows, columns },
+                board.clear_completed_lines()
+            );
+            assert_eq!(expected, board.contents());
+            let rebuilt = Canvas::from_contents(board.rows, board.columns, expected);
+            assert_eq!(rebuilt.filled, board.filled);
+            assert_eq!(rebuilt.marked, board.marked);
+            assert_eq!(
+                board.contents().iter().all(|p| *p == PointStatus::Empty),
+                board.is_empty()
+            );
+        }
+    }
+
+    #[test]
+    fn row_masks_find_the_same_fit_as_a_scan() {
+        let mut blocks = Block::catalog_all_orientations();
+        blocks.push(Block::from_coords(vec![Point { x: 0, y: 0 }, Point { x: 2, y: 1 }]).unwrap());
+        let mut shifted = Block::tee();
+        shifted.translate(-1, -1);
+        blocks.push(shifted);
+        // wider and taller than any board below
+        blocks.push(Block::from_coords(vec![Point { x: 0, y: 0 }, Point { x: 64, y: 0 }]).unwrap());
+        blocks
+            .push(Block::from_coords(vec![Point { x: 0, y: 0 }, Point { x: 0, y: 1000 }]).unwrap());
+
+        let mut boards = random_boards();
+        boards.push(Canvas::new(8, 64));
+        boards.push(Canvas::new(0, 0));
+        for board in boards {
+            for block in &blocks {
+                let fit = board
+                    .can_fit(block)
+                    .map(|playable| (playable.row, playable.column));
+                assert_eq!(board.can_fit_by_scan(block), fit, "{block} on\n{board:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn renders_block_colors_as_ansi() {
+        let mut board = Canvas::new(2, 3);
+        let red = Block::rectangle(1, 1).with_color(1);
+        let green = Block::rectangle(1, 1).with_color(2);
+        board.add(&board.try_make_playable(&red, 1, 0).unwrap());
+        board.add(&board.try_make_playable(&green, 0, 2).unwrap());
+
+        assert_eq!(
+            concat!(
+                "\x1b[91m▅\x1b[0m \x1b[2m.\x1b[0m \x1b[2m.\x1b[0m\n",
+                "\x1b[2m.\x1b[0m \x1b[2m.\x1b[0m \x1b[92m▅\x1b[0m\n",
+            ),
+            board.render_ansi(true)
+        );
+        assert_eq!("▅ . .\n. . ▅\n", board.render_ansi(false));
+    }
+
+    #[test]
+    fn displays_a_plain_grid() {
+        let board = Canvas::from_str_grid(
+            "
+            . ▅ .
+            ⏲ ⏲ ⏲
+            ",
+        )
+        .unwrap();
+
+        assert_eq!(". ▅ .\n⏲ ⏲ ⏲\n", format!("{}", board));
+        assert_ne!(format!("{}", board), format!("{:?}", board));
+    }
+
+    #[test]
+    fn can_check_for_full_lines() {
+        let board = Canvas::from_str_grid(
+            "
+            . . ▅ .
+            ▅ ▅ ▅ ▅
+            . . ▅ .
+            ▅ ▅ ▅ ▅
+            ",
+        )
+        .unwrap();
+
+        assert_eq!(vec![0, 2], board.check_rows());
+        assert_eq!(vec![2], board.check_columns());
+        assert!(Canvas::default().check_rows().is_empty());
+        assert!(Canvas::default().check_columns().is_empty());
+    }
+
+    #[test]
+    fn gravity_closes_gaps_in_columns() {
+        let mut board = Canvas::new(5, 2);
+        board.set(4, 0, PointStatus::Occupied(3));
+        board.set(2, 0, PointStatus::Occupied(2));
+        board.set(0, 0, PointStatus::Occupied(1));
+        board.set(3, 1, PointStatus::Occupied(4));
+
+        let generation = board.generation();
+        board.apply_gravity();
+        assert!(board.generation() > generation);
+
+        let expected = [
+            (0, 0, PointStatus::Occupied(1)),
+            (1, 0, PointStatus::Occupied(2)),
+            (2, 0, PointStatus::Occupied(3)),
+            (0, 1, PointStatus::Occupied(4)),
+        ];
+        for (row, column, status) in board.iter_cells() {
+            let want = expected
+                .iter()
+                .find(|(r, c, _)| (*r, *c) == (row, column))
+                .map_or(PointStatus::Empty, |(_, _, status)| *status);
+            assert_eq!(want, status, "cell {row}, {column}");
+        }
+
+        let generation = board.generation();
+        board.apply_gravity();
+        assert_eq!(generation, board.generation());
+    }
+
+    #[test]
+    fn counts_buried_empty_cells_as_holes() {
+        let buried = Canvas::from_str_grid(
+            "
+            . . .
+            . ▅ .
+            . . ▅
+            ▅ ▅ ▅
+            ",
+        )
+        .unwrap();
+        assert_eq!(1, buried.count_holes());
+
+        let clean = Canvas::from_str_grid(
+            "
+            . . .
+            . ▅ .
+            ▅ ▅ .
+            ▅ ▅ ▅
+            ",
+        )
+        .unwrap();
+        assert_eq!(0, clean.count_holes());
+        assert_eq!(0, Canvas::default().count_holes());
+    }
+
+    #[test]
+    fn measures_column_heights_and_bumpiness() {
+        let board = Canvas::from_str_grid(
+            "
+            . . . .
+            ▅ . . .
+            . . ▅ .
+            ▅ . ▅ ▅
+            ",
+        )
+        .unwrap();
+
+        assert_eq!(vec![3, 0, 2, 1], board.column_heights());
+        assert_eq!(6, board.aggregate_height());
+        assert_eq!(3 + 2 + 1, board.bumpiness());
+
+        let flat = Canvas::from_str_grid("▅ ▅ ▅").unwrap();
+        assert_eq!(vec![1, 1, 1], flat.column_heights());
+        assert_eq!(3, flat.aggregate_height());
+        assert_eq!(0, flat.bumpiness());
+        assert_eq!(0, Canvas::default().aggregate_height());
+    }
+
+    #[test]
+    fn boards_compare_by_shape_and_contents() {
+        let mut built = Canvas::new(4, 4);
+        built.add(&built.try_make_playable(&Block::line(3), 1, 0).unwrap());
+        let mut placed = Canvas::new(4, 4);
+        for x in 0..3 {
+            placed.set(1, x, PointStatus::Occupied(Block::line(3).color()));
+        }
+        assert_eq!(built, placed);
+
+        placed.set(3, 3, PointStatus::Occupied(0));
+        assert_ne!(built, placed);
+
+        let mut marked = built.clone();
+        marked.set(1, 0, PointStatus::MarkedForRemoval(1));
+        assert_ne!(built, marked);
+        assert_ne!(Canvas::new(4, 2), Canvas::new(2, 4));
+    }
+
+    #[test]
+    fn can_fit_after_rotating() {
+        // only a vertical slot is free
+        let mut board = Canvas::new(4, 4);
+        board.fill(PointStatus::Occupied(1));
+        for y in 0..3 {
+            board.set(y, 2, PointStatus::Empty);
+        }
+
+        let line = Block::line(3);
+        assert!(board.can_fit(&line).is_none());
+
+        let rotated = board.can_fit_any_rotation(&line).unwrap();
+        assert_eq!(1, rotated.dimensions().width);
+        assert_eq!(3, rotated.dimensions().height);
+        let playable = board.can_fit_in_any_rotation(&line).unwrap();
+        assert_eq!(
+            vec![2; 3],
+            playable
+                .block()
+                .at(playable.row, playable.column)
+                .iter()
+                .map(|p| p.x)
+                .collect::<Vec<_>>()
+        );
+
+        assert!(board.can_fit_any_rotation(&Block::line(4)).is_none());
+    }
+
+    #[test]
+    fn snapshot_reports_cells_by_row_and_column() {
+        let mut board = Canvas::new(2, 3);
+        board.add(
+            &board
+                .try_make_playable(&Block::line(2).with_color(4), 0, 0)
+                .unwrap(),
+        );
+        board.add(
+            &board
+                .try_make_playable(&Block::rectangle(1, 1).with_color(7), 1, 2)
+                .unwrap(),
+        );
+
+        let empty = CellInfo::default();
+        let cell = |id| CellInfo {
+            occupied: true,
+            block_id: Some(id),
+        };
+        assert_eq!(
+            vec![vec![cell(4), cell(4), empty], vec![empty, empty, cell(7)]],
+            board.snapshot()
+        );
+
+        board.set(1, 0, PointStatus::MarkedForRemoval(1));
+        assert_eq!(
+            CellInfo {
+                occupied: true,
+                block_id: None
+            },
+            board.snapshot()[1][0]
+        );
+    }
+
+    #[test]
+    fn place_adds_and_clears() {
+        let mut board = Canvas::new(4, 4);
+        assert_eq!(Ok(0), board.place(&Block::line(3), 0, 0));
+        assert_eq!(3, board.count_occupied());
+
+        assert_eq!(Ok(1), board.place(&Block::rectangle(1, 2), 0, 3));
+        assert_eq!(1, board.count_occupied());
+        assert!(matches!(board.get(1, 3), Some(PointStatus::Occupied(_))));
+    }
+
+    #[test]
+    fn place_rejects_blocks_that_do_not_fit() {
+        let mut board = Canvas::new(4, 4);
+        board.place(&Block::rectangle(1, 1), 2, 2).unwrap();
+        let before = board.clone();
+
+        assert_eq!(
+            Err(PlaceError::Overlap),
+            board.place(&Block::rectangle(2, 2), 1, 1)
+        );
+        assert_eq!(
+            Err(PlaceError::OutOfBounds),
+            board.place(&Block::line(3), 0, 2)
+        );
+        assert_eq!(before, board);
+    }
+
+    #[test]
+    fn placement_errors_say_why() {
+        let mut board = Canvas::new(4, 4);
+        board.set(1, 1, PointStatus::Occupied(0));
+
+        let square = Block::rectangle(2, 2);
+        assert_eq!(
+            Err(PlaceError::OutOfBounds),
+            board.make_playable(&square, 3, 0).map(|_| ())
+        );
+        assert_eq!(
+            Err(PlaceError::OutOfBounds),
+            board.make_playable(&square, 0, -1).map(|_| ())
+        );
+        assert_eq!(
+            Err(PlaceError::Overlap),
+            board.make_playable(&square, 0, 0).map(|_| ())
+        );
+        // off the edge and overlapping at once
+        assert_eq!(
+            Err(PlaceError::OutOfBounds),
+            board.make_playable(&Block::line(4), 1, 1).map(|_| ())
+        );
+        assert!(board.make_playable(&square, 2, 2).is_ok());
+        assert_eq!(
+            "block would leave the board",
+            PlaceError::OutOfBounds.to_string()
+        );
+    }
+
+    #[test]
+    fn marking_keeps_cells_until_cleared() {
+        let mut board = Canvas::from_str_grid(
+            "
+            . . ▅
+            . . ▅
+            ▅ ▅ ▅
+            ",
+        )
+        .unwrap();
+
+        assert_eq!(2, board.mark_completed_lines());
+        assert_eq!(
+            vec![(0, 0), (0, 1), (0, 2), (1, 2), (2, 2)],
+            board.marked_cells()
+        );
+        assert_eq!(5, board.count_occupied());
+
+        // already marked lines still count as complete
+        assert_eq!(2, board.clear_completed_lines().total());
+        assert!(board.marked_cells().is_empty());
+        assert!(board.is_empty());
+    }
+
+    #[test]
+    fn remarking_keeps_pending_dwell() {
+        let mut board = Canvas::new(4, 4);
+        let line = Block::line(4);
+        board.add(&board.try_make_playable(&line, 0, 0).unwrap());
+        assert_eq!(1, board.mark_completed_lines_with_dwell(3).total());
+
+        // an already marked line is not marked again
+        assert_eq!(0, board.mark_completed_lines_with_dwell(1).total());
+        assert_eq!(0, board.tick_marks());
+        assert_eq!(0, board.tick_marks());
+        assert_eq!(4, board.tick_marks());
+        assert!(board.is_empty());
     }
 }
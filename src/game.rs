@@ -1,30 +1,500 @@
 //! Holds high-level game logic using components defined elsewhere in the crate.
 
+use std::cmp::Reverse;
+use std::collections::HashSet;
 use std::fmt::{Debug, Display};
+use std::ops::{Deref, DerefMut};
+use std::sync::{Mutex, PoisonError};
 
-use crate::{block::Block, canvas::Canvas};
-use rand::{rng, seq::SliceRandom};
+use crate::{
+    block::{Block, Point, Variant},
+    canvas::{Canvas, PlaceError},
+};
+use rand::{Rng, RngCore, SeedableRng, rng};
+use rand_chacha::ChaCha12Rng;
 
 const POINTS_PER_LINE_CLEAR: usize = 50;
 
+/// Number of blocks handed to the player at a time.
+const TRAY_SIZE: usize = 3;
+
+/// Extra points for each line beyond the first cleared by a single placement.
+const MULTI_LINE_BONUS: usize = 25;
+
+/// Number of independently drawn trays [`Game::generate_solvable_tray`] checks before falling back
+/// to [`Game::generate_blocks`].
+const SOLVABLE_TRAY_ATTEMPTS: usize = 50;
+
+/// Points for a placement which leaves the board empty.
+const PERFECT_CLEAR_BONUS: usize = 1000;
+
+/// Weight given to the latest move in the rolling average of lines cleared per move.
+const DIFFICULTY_SMOOTHING: f32 = 0.2;
+
+/// How far difficulty moves after each placement.
+const DIFFICULTY_STEP: f32 = 0.05;
+
+/// Fixed bias applied to block generation when [`Game::adaptive_difficulty`] is not enabled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Difficulty {
+    /// Favors small rectangles and lines.
+    Easy,
+    /// Every block is equally likely, counting the pentominoes as one, see
+    /// [`Block::standard_set_weight`].
+    #[default]
+    Normal,
+    /// Favors large and awkward blocks, see [`Block::placement_weight`].
+    Hard,
+}
+
+impl Difficulty {
+    /// Relative likelihood of generating `block` at this difficulty.
+    pub fn weight(&self, block: &Block) -> f32 {
+        match self {
+            Difficulty::Easy => match block.variant() {
+                Variant::Rectangle | Variant::Line if block.cell_count() <= 4 => 4.0,
+                _ => 1.0,
+            },
+            Difficulty::Normal => 1.0,
+            Difficulty::Hard => block.placement_weight() as f32,
+        }
+    }
+}
+
+/// Tracks how quickly the player clears lines and adjusts difficulty to keep them near
+/// `target_rate` lines cleared per move.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AdaptiveDifficulty {
+    pub target_rate: f32,
+    difficulty: f32,
+    rate: f32,
+}
+
+impl AdaptiveDifficulty {
+    /// Start at medium difficulty, assuming the player is already on target.
+    pub fn new(target_rate: f32) -> Self {
+        Self {
+            target_rate,
+            difficulty: 0.5,
+            rate: target_rate,
+        }
+    }
+
+    /// Current difficulty, from 0.0 (favors small blocks) to 1.0 (favors large blocks).
+    pub fn difficulty(&self) -> f32 {
+        self.difficulty
+    }
+
+    fn record(&mut self, lines_cleared: usize) {
+        self.rate += DIFFICULTY_SMOOTHING * (lines_cleared as f32 - self.rate);
+
+        if self.rate > self.target_rate {
+            self.difficulty += DIFFICULTY_STEP;
+        } else if self.rate < self.target_rate {
+            self.difficulty -= DIFFICULTY_STEP;
+        }
+        self.difficulty = self.difficulty.clamp(0.0, 1.0);
+    }
+}
+
+/// Points awarded for clearing lines.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ScoreConfig {
+    /// Points for each line cleared.
+    pub per_line: usize,
+    /// How much the multiplier grows with each placement in a combo, see [`Game::combo`]. The
+    /// first clear of a combo is always worth its points once.
+    pub combo_step: usize,
+    /// Extra points for each line beyond the first cleared by a single placement.
+    pub multi_clear_bonus: usize,
+}
+
+impl Default for ScoreConfig {
+    fn default() -> Self {
+        Self {
+            per_line: POINTS_PER_LINE_CLEAR,
+            combo_step: 1,
+            multi_clear_bonus: MULTI_LINE_BONUS,
+        }
+    }
+}
+
+impl ScoreConfig {
+    /// Points for clearing `lines_cleared` lines with a single placement as the `combo`th
+    /// placement of a combo.
+    fn points(&self, lines_cleared: usize, combo: usize) -> usize {
+        if lines_cleared == 0 {
+            return 0;
+        }
+
+        let multiplier = 1 + combo.saturating_sub(1) * self.combo_step;
+        let points = lines_cleared * self.per_line + (lines_cleared - 1) * self.multi_clear_bonus;
+        points * multiplier
+    }
+}
+
+/// Result of placing a single block.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MoveOutcome {
+    pub row: i32,
+    pub column: i32,
+    pub lines_cleared: usize,
+    /// Points the move added to the score.
+    pub points: usize,
+}
+
+/// A placement recorded in [`Game::moves`], enough to repeat it with [`Game::replay`].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Move {
+    /// The block's shape, see [`Block::canonical`].
+    pub shape: Vec<Point>,
+    /// Quarter turns to the right that take `shape` to the orientation the block was placed in.
+    pub rotation: i32,
+    /// Row of the bottom edge of the placed block.
+    pub row: i32,
+    /// Column of the left edge of the placed block.
+    pub column: i32,
+    pub variant: Variant,
+    pub color: u8,
+}
+
+impl Move {
+    fn new(block: &Block, row: i32, column: i32) -> Self {
+        let shape = block.canonical();
+        let placed = oriented(block);
+        let rotation = (0..4)
+            .find(|&turns| {
+                let mut candidate = Block::from_parts(shape.clone(), Variant::Custom, 0);
+                oriented(candidate.rotate_n(turns)) == placed
+            })
+            .unwrap_or(0);
+        let (min, _) = block.bounding_box();
+
+        Move {
+            shape,
+            rotation,
+            row: row + min.y,
+            column: column + min.x,
+            variant: block.variant().clone(),
+            color: block.color(),
+        }
+    }
+
+    /// The block as it was placed, normalized so that [`Move::row`] and [`Move::column`] are its
+    /// origin.
+    pub fn block(&self) -> Block {
+        let mut block = Block::from_parts(self.shape.clone(), self.variant.clone(), self.color);
+        block.rotate_n(self.rotation).normalize();
+        block
+    }
+}
+
+/// Normalized, sorted coordinates of `block` in its current orientation.
+fn oriented(block: &Block) -> Vec<Point> {
+    let mut block = block.clone();
+    block.normalize();
+    let mut coords = block.coordinates().clone();
+    coords.sort();
+    coords
+}
+
+/// Outcome of a self-played game, see [`Game::play_random`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct GameSummary {
+    pub score: usize,
+    pub moves: usize,
+    pub game_over: bool,
+}
+
+/// Fields missing from a save, such as those added after it was written, are loaded with their
+/// [`Default`] values.
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
 pub struct Game {
-    pub canvas: Canvas,
+    canvas: Canvas,
     pub score: usize,
+    /// When the fraction of occupied cells reaches this threshold, generated trays include at
+    /// least one block that completes a line, provided such a block exists.
+    pub assist_threshold: Option<f32>,
+    /// Points awarded for every cell of a placed block, on top of any line clears.
+    pub points_per_cell: usize,
+    /// When set, placing a block awards its [`Block::placement_weight`] in points.
+    pub award_placement_weight: bool,
+    /// When set, block generation adapts to how quickly the player clears lines.
+    pub adaptive_difficulty: Option<AdaptiveDifficulty>,
+    /// How line clears are scored.
+    pub scoring: ScoreConfig,
+    /// Bias applied to block generation, ignored while [`Game::adaptive_difficulty`] is set.
+    pub difficulty_tier: Difficulty,
+    /// When unset, blocks must be placed as generated: generation only hands out blocks which fit
+    /// without turning, and move search never turns them. On by default.
+    pub rotation_allowed: bool,
+    combo: usize,
+    /// Blocks handed to the player that have not been used yet.
+    tray: Vec<Block>,
+    /// Block set aside for later, see [`Game::hold`].
+    held: Option<Block>,
+    /// Every placement since the last reset, oldest first.
+    moves: Vec<Move>,
+    /// Unset once the board is edited through [`Game::board_mut`], after which `moves` no longer
+    /// describes how the board was reached.
+    replayable: bool,
+    /// Source of randomness for block generation when the game is seeded, see
+    /// [`Game::with_seed`]. Saved along with its position in the stream, so a loaded game keeps
+    /// generating the same blocks.
+    seeded_rng: Option<Mutex<ChaCha12Rng>>,
+}
+
+impl Default for Game {
+    fn default() -> Self {
+        Self {
+            canvas: Canvas::default(),
+            score: 0,
+            assist_threshold: None,
+            points_per_cell: 0,
+            award_placement_weight: false,
+            adaptive_difficulty: None,
+            scoring: ScoreConfig::default(),
+            difficulty_tier: Difficulty::default(),
+            rotation_allowed: true,
+            combo: 0,
+            tray: Vec::new(),
+            held: None,
+            moves: Vec::new(),
+            replayable: true,
+            seeded_rng: None,
+        }
+    }
+}
+
+/// Mutable access to a [`Game`]'s board, see [`Game::board_mut`]. Dereferences to the
+/// [`Canvas`].
+pub struct BoardMut<'a> {
+    game: &'a mut Game,
+    edited: bool,
+}
+
+impl Deref for BoardMut<'_> {
+    type Target = Canvas;
+
+    fn deref(&self) -> &Canvas {
+        &self.game.canvas
+    }
+}
+
+impl DerefMut for BoardMut<'_> {
+    fn deref_mut(&mut self) -> &mut Canvas {
+        self.edited = true;
+        &mut self.game.canvas
+    }
+}
+
+impl Drop for BoardMut<'_> {
+    fn drop(&mut self) {
+        if self.edited {
+            self.game.replayable = false;
+        }
+    }
 }
 
 impl Game {
+    /// Create a game whose block generation is reproducible: two games with the same seed
+    /// generate the same blocks, given the same sequence of placements.
+    pub fn with_seed(seed: u64) -> Self {
+        Self {
+            seeded_rng: Some(Mutex::new(ChaCha12Rng::seed_from_u64(seed))),
+            ..Default::default()
+        }
+    }
+
+    /// Serialize the whole game, board, score, combo and tray included, as JSON.
+    #[cfg(feature = "serde")]
+    pub fn save(&self) -> String {
+        serde_json::to_string(self).expect("game state is always serializable")
+    }
+
+    /// Restore a game written by [`Game::save`].
+    #[cfg(feature = "serde")]
+    pub fn load(s: &str) -> Result<Game, serde_json::Error> {
+        serde_json::from_str(s)
+    }
+
+    /// The playing board.
+    pub fn board(&self) -> &Canvas {
+        &self.canvas
+    }
+
+    /// Mutable access to the playing board, for editors and tests which need to set up a
+    /// position directly.
+    ///
+    /// Edits made here are not placements: they leave the score, combo and [`Game::moves`] as
+    /// they were. Since the move log can then no longer rebuild the board, the first edit marks
+    /// the game as not [`Game::is_replayable`] until the next [`Game::reset`].
+    pub fn board_mut(&mut self) -> BoardMut<'_> {
+        BoardMut {
+            game: self,
+            edited: false,
+        }
+    }
+
+    /// Returns false if the board has been edited through [`Game::board_mut`] since the game
+    /// started or was last reset, in which case [`Game::replay`] of [`Game::moves`] does not
+    /// reproduce this board.
+    pub fn is_replayable(&self) -> bool {
+        self.replayable
+    }
+
+    /// Current difficulty when [`Game::adaptive_difficulty`] is enabled, see
+    /// [`AdaptiveDifficulty::difficulty`].
+    pub fn difficulty(&self) -> Option<f32> {
+        self.adaptive_difficulty
+            .as_ref()
+            .map(AdaptiveDifficulty::difficulty)
+    }
+
+    /// Number of placements in a row that have cleared at least one line. Line clears are
+    /// worth more the longer the combo, see [`ScoreConfig::combo_step`].
+    pub fn combo(&self) -> usize {
+        self.combo
+    }
+
     pub fn reset(&mut self) -> &mut Self {
         self.canvas.clear_all();
         self.score = 0;
+        self.combo = 0;
+        self.tray.clear();
+        self.held = None;
+        self.moves.clear();
+        self.replayable = true;
+        if let Some(adaptive) = &mut self.adaptive_difficulty {
+            *adaptive = AdaptiveDifficulty::new(adaptive.target_rate);
+        }
         self
     }
 
+    /// Every placement made since the game started or was last reset, oldest first.
+    pub fn moves(&self) -> &[Move] {
+        &self.moves
+    }
+
+    /// Rebuild a game by placing `moves` in order on this one, which should start from the same
+    /// board and settings, such as [`Game::points_per_cell`], as the recorded game did, since these
+    /// decide where blocks fit and how they score.
+    ///
+    /// Placing recorded moves draws no randomness, so the seed only matters for the blocks
+    /// generated after the replay; seed this game like the recorded one for those to match.
+    ///
+    /// Returns the error of the first move which cannot be placed, e.g. because the recorded game
+    /// was started differently or was not [`Game::is_replayable`].
+    pub fn replay(mut self, moves: &[Move]) -> Result<Game, PlaceError> {
+        for recorded in moves {
+            self.maybe_place_block(&recorded.block(), recorded.row, recorded.column)?;
+        }
+
+        Ok(self)
+    }
+
+    /// The block set aside with [`Game::hold`], if any.
+    pub fn held(&self) -> Option<&Block> {
+        self.held.as_ref()
+    }
+
+    /// Set `block` aside for later, returning the block that was held before it.
+    pub fn hold(&mut self, block: Block) -> Option<Block> {
+        self.held.replace(block)
+    }
+
+    /// Generate a fresh tray of three blocks that fit on the board together.
+    pub fn new_tray(&self) -> Option<Vec<Block>> {
+        self.generate_blocks(TRAY_SIZE)
+    }
+
+    /// The blocks the player has left to place, refilled with [`Game::new_tray`] once all of
+    /// them have been taken.
+    pub fn tray(&mut self) -> &[Block] {
+        if self.tray.is_empty()
+            && let Some(tray) = self.new_tray()
+        {
+            self.tray = tray;
+        }
+
+        &self.tray
+    }
+
+    /// Remove and return the block at `index` of [`Game::tray`], or `None` if there is no such
+    /// block.
+    pub fn take_from_tray(&mut self, index: usize) -> Option<Block> {
+        if index >= self.tray().len() {
+            return None;
+        }
+
+        Some(self.tray.remove(index))
+    }
+
     /// Generate `n` blocks that are guaranteed to fit within the available playing area.
     pub fn generate_blocks(&self, n: usize) -> Option<Vec<Block>> {
+        self.with_rng(|rng| self.generate_blocks_with(n, rng))
+    }
+
+    /// Generate `n` blocks which can all be placed on the current board, in some order and
+    /// rotation. Unlike [`Game::generate_blocks`], each block is drawn as if it were the only one,
+    /// so later blocks are not squeezed into whatever space the earlier ones leave.
+    ///
+    /// Returns `None` if no block fits on the board at all.
+    pub fn generate_solvable_tray(&self, n: usize) -> Option<Vec<Block>> {
+        self.with_rng(|rng| self.generate_solvable_tray_with(n, rng))
+    }
+
+    fn generate_solvable_tray_with<R: Rng + ?Sized>(
+        &self,
+        n: usize,
+        rng: &mut R,
+    ) -> Option<Vec<Block>> {
+        for _ in 0..SOLVABLE_TRAY_ATTEMPTS {
+            let tray = self.draw_tray_with(n, rng, |game, _, rng| {
+                game.generate_block_with(&mut game.canvas.clone(), rng)
+            })?;
+
+            if can_place_all(&self.canvas, &tray, self.rotation_allowed) {
+                return Some(tray);
+            }
+        }
+
+        // Blocks generated one after another always fit together.
+        self.generate_blocks_with(n, rng)
+    }
+
+    /// Same as [`Game::generate_blocks`], drawing randomness from `rng`.
+    fn generate_blocks_with<R: Rng + ?Sized>(&self, n: usize, rng: &mut R) -> Option<Vec<Block>> {
+        self.draw_tray_with(n, rng, |game, shadow_canvas, rng| {
+            game.generate_block_with(shadow_canvas, rng)
+        })
+    }
+
+    /// Draw a tray of `n` blocks, starting with an assisting block when the board is past
+    /// `assist_threshold` and filling the rest with `draw`. `draw` is handed a copy of the board
+    /// holding every block drawn so far, and returns `None` when no more blocks fit.
+    fn draw_tray_with<R: Rng + ?Sized>(
+        &self,
+        n: usize,
+        rng: &mut R,
+        draw: impl Fn(&Self, &mut Canvas, &mut R) -> Option<Block>,
+    ) -> Option<Vec<Block>> {
         let mut blocks = Vec::new();
         let mut shadow_canvas = self.canvas.clone();
-        for _ in 0..n {
-            if let Some(generated_block) = self.naive_generate_block(&mut shadow_canvas) {
+        if n > 0 {
+            blocks.extend(self.generate_assisting_block(&mut shadow_canvas, rng));
+        }
+
+        while blocks.len() < n {
+            if let Some(generated_block) = draw(self, &mut shadow_canvas, rng) {
                 blocks.push(generated_block);
             } else {
                 // no more blocks could fit!
@@ -37,76 +507,1283 @@ impl Game {
     }
 
     pub fn naive_generate_block(&self, canvas: &mut Canvas) -> Option<Block> {
-        let mut all_blocks = [
-            Block::rectangle(3, 3),
-            Block::rectangle(3, 2),
-            Block::rectangle(2, 3),
-            Block::rectangle(2, 2),
-            Block::rectangle(1, 1),
-            Block::tee(),
-            Block::line(2),
-            Block::line(3),
-            Block::line(4),
-            Block::line(5),
-            Block::elle(3, 3),
-            Block::elle(3, 2),
-            Block::elle(2, 3),
-            Block::elle(2, 2),
-            Block::diagonal(2),
-            Block::diagonal(3),
-            Block::diagonal(4),
-        ];
+        self.with_rng(|rng| self.generate_block_with(canvas, rng))
+    }
 
-        let mut rng = rng();
-        all_blocks.shuffle(&mut rng);
-        for block in &mut all_blocks {
-            for _ in (0..360).step_by(90) {
-                if let Some(playable) = canvas.can_fit(&block) {
-                    canvas.add(&playable);
-                    return Some(block.to_owned());
-                }
-                block.rotate_left();
+    /// Run `f` with the seeded generator if there is one, otherwise the thread-local one.
+    fn with_rng<T>(&self, f: impl FnOnce(&mut dyn RngCore) -> T) -> T {
+        match &self.seeded_rng {
+            Some(seeded) => f(&mut *seeded.lock().unwrap_or_else(PoisonError::into_inner)),
+            None => f(&mut rng()),
+        }
+    }
+
+    fn generate_block_with<R: Rng + ?Sized>(
+        &self,
+        canvas: &mut Canvas,
+        rng: &mut R,
+    ) -> Option<Block> {
+        let mut all_blocks = Block::standard_set();
+        match (self.difficulty(), self.difficulty_tier) {
+            (Some(difficulty), _) => {
+                // larger blocks come first as difficulty approaches 1.0, smaller towards 0.0
+                let exponent = 2.0 * difficulty - 1.0;
+                weighted_shuffle(
+                    &mut all_blocks,
+                    |block| (block.cell_count() as f32).powf(exponent),
+                    rng,
+                )
+            }
+            (None, tier) => weighted_shuffle(&mut all_blocks, |block| tier.weight(block), rng),
+        }
+
+        for block in &all_blocks {
+            let fit = if self.rotation_allowed {
+                canvas.can_fit_in_any_rotation(block)
+            } else {
+                canvas.can_fit(block)
+            };
+
+            if let Some(playable) = fit {
+                canvas.add(&playable);
+                return Some(playable.block().clone());
             }
         }
 
         None
     }
 
-    pub fn maybe_place_block(&mut self, block: &Block, row: i32, column: i32) -> Result<(), &str> {
-        let Some(playable) = self.canvas.try_make_playable(block, row, column) else {
-            return Err("Unable to place block.");
-        };
+    /// Generate a block that completes a line on `canvas` if the board is at least as full as
+    /// `assist_threshold`, adding it to `canvas` where it completes the most lines.
+    fn generate_assisting_block<R: Rng + ?Sized>(
+        &self,
+        canvas: &mut Canvas,
+        rng: &mut R,
+    ) -> Option<Block> {
+        let threshold = self.assist_threshold?;
+        if canvas.occupancy() < threshold {
+            return None;
+        }
+
+        let mut all_blocks = Block::standard_set();
+        weighted_shuffle(&mut all_blocks, |_| 1.0, rng);
+        for block in &all_blocks {
+            let Some((cleared, rotated, row, column)) =
+                Self::best_move_on(canvas, block, self.quarter_turns())
+            else {
+                continue;
+            };
+
+            if cleared > 0 {
+                let playable = canvas.try_make_playable(&rotated, row, column)?;
+                canvas.add(&playable);
+                return Some(rotated);
+            }
+        }
+
+        None
+    }
 
+    pub fn maybe_place_block(
+        &mut self,
+        block: &Block,
+        row: i32,
+        column: i32,
+    ) -> Result<(), PlaceError> {
+        self.place(block, row, column).map(|_| ())
+    }
+
+    fn place(&mut self, block: &Block, row: i32, column: i32) -> Result<MoveOutcome, PlaceError> {
+        let playable = self.canvas.make_playable(block, row, column)?;
+
+        let score_before = self.score;
         self.canvas.add(&playable);
-        let lines_cleared = self.canvas.clear_completed_lines();
+        self.moves.push(Move::new(block, row, column));
+        self.score += block.cell_count() * self.points_per_cell;
+        if self.award_placement_weight {
+            self.score += block.placement_weight();
+        }
+        let lines_cleared = self.canvas.clear_completed_lines().total();
         self.update_score(lines_cleared);
+        if lines_cleared > 0 && self.canvas.is_empty() {
+            self.score += PERFECT_CLEAR_BONUS;
+        }
+        if let Some(adaptive) = self.adaptive_difficulty.as_mut() {
+            adaptive.record(lines_cleared);
+        }
+
+        Ok(MoveOutcome {
+            row,
+            column,
+            lines_cleared,
+            points: self.score - score_before,
+        })
+    }
+
+    /// Place each of `pieces` in turn using [`Game::best_move`], stopping at the first piece
+    /// which can't be placed. Returns the outcome of every move made.
+    pub fn auto_play(&mut self, pieces: &[Block]) -> Vec<MoveOutcome> {
+        let mut outcomes = Vec::new();
+
+        for block in pieces {
+            let Some((rotated, row, column)) = self.best_move(block) else {
+                break;
+            };
+
+            match self.place(&rotated, row, column) {
+                Ok(outcome) => outcomes.push(outcome),
+                Err(_) => break,
+            }
+        }
 
-        Ok(())
+        outcomes
+    }
+
+    /// Find the rotation and origin of `block` that clears the most lines, returned as
+    /// `(rotated_block, row, column)`. Ties go to the move leaving the fewest occupied cells, then
+    /// the lowest row, then the lowest column.
+    ///
+    /// Returns `None` if the block does not fit anywhere in any rotation.
+    pub fn best_move(&self, block: &Block) -> Option<(Block, i32, i32)> {
+        Self::best_move_on(&self.canvas, block, self.quarter_turns())
+            .map(|(_, block, row, column)| (block, row, column))
+    }
+
+    /// Same as [`Game::best_move`] on an arbitrary canvas, trying the first `turns` rotations and
+    /// also returning the number of lines the move completes.
+    fn best_move_on(
+        canvas: &Canvas,
+        block: &Block,
+        turns: usize,
+    ) -> Option<(usize, Block, i32, i32)> {
+        let mut rotated = block.clone();
+        let mut best: Option<(MoveRank, Block)> = None;
+
+        for _ in 0..turns {
+            for (row, column) in canvas.find_placements(&rotated) {
+                let mut preview = canvas.clone();
+                let cleared = preview
+                    .place(&rotated, row, column)
+                    .expect("found placements always fit");
+                let rank = (Reverse(cleared), preview.count_occupied(), row, column);
+
+                if best.as_ref().is_none_or(|(best_rank, _)| rank < *best_rank) {
+                    best = Some((rank, rotated.clone()));
+                }
+            }
+            rotated.rotate_left();
+        }
+
+        best.map(|((Reverse(cleared), _, row, column), block)| (cleared, block, row, column))
+    }
+
+    /// Same as [`Game::best_move`], kept for callers which ask for a placement hint.
+    pub fn best_placement(&self, block: &Block) -> Option<(Block, i32, i32)> {
+        self.best_move(block)
+    }
+
+    /// Returns true if no block in `tray` fits anywhere on the board in any rotation. An empty
+    /// tray is considered over, so refill it before asking.
+    pub fn is_game_over(&self, tray: &[Block]) -> bool {
+        !tray.iter().any(|block| {
+            if self.rotation_allowed {
+                self.canvas.can_fit_any_rotation(block).is_some()
+            } else {
+                self.canvas.can_fit(block).is_some()
+            }
+        })
+    }
+
+    /// Number of rotations move search tries, see [`Game::rotation_allowed`].
+    fn quarter_turns(&self) -> usize {
+        if self.rotation_allowed { 4 } else { 1 }
+    }
+
+    /// Returns true if every placement of every block in `tray`, in any rotation, leaves more
+    /// holes on the board than there are now, i.e. the player would be better off rerolling.
+    pub fn should_reroll(&self, tray: &[Block]) -> bool {
+        let holes = self.canvas.count_holes();
+
+        for block in tray {
+            let mut rotated = block.clone();
+            for _ in 0..self.quarter_turns() {
+                for row in 0..self.canvas.rows as i32 {
+                    for column in 0..self.canvas.columns as i32 {
+                        let Some(playable) = self.canvas.try_make_playable(&rotated, row, column)
+                        else {
+                            continue;
+                        };
+
+                        let mut preview = self.canvas.clone();
+                        preview.add(&playable).clear_completed_lines();
+                        if preview.count_holes() <= holes {
+                            return false;
+                        }
+                    }
+                }
+                rotated.rotate_left();
+            }
+        }
+
+        true
+    }
+
+    /// Estimate how many more blocks can be placed by repeatedly cycling through `tray` and
+    /// placing each block with [`Game::best_move`] on a copy of the board until one no longer
+    /// fits.
+    ///
+    /// The estimate is capped at the number of cells on the board, as a board which keeps
+    /// clearing lines could otherwise go on forever.
+    pub fn estimate_moves_remaining(&self, tray: &[Block]) -> usize {
+        let limit = self.canvas.rows * self.canvas.columns;
+        let mut canvas = self.canvas.clone();
+        let mut moves = 0;
+
+        for block in tray.iter().cycle() {
+            if moves >= limit {
+                break;
+            }
+
+            let Some((_, rotated, row, column)) =
+                Self::best_move_on(&canvas, block, self.quarter_turns())
+            else {
+                break;
+            };
+            let Some(playable) = canvas.try_make_playable(&rotated, row, column) else {
+                break;
+            };
+
+            canvas.add(&playable).clear_completed_lines();
+            moves += 1;
+        }
+
+        moves
+    }
+
+    /// Play a game against itself from an empty board, placing each generated block with
+    /// [`Game::best_move`] until `max_moves` blocks are placed or no block can be placed.
+    ///
+    /// The same `seed` always produces the same game.
+    pub fn play_random(seed: u64, max_moves: usize) -> GameSummary {
+        let mut game = Game::with_seed(seed);
+        let mut moves = 0;
+
+        while moves < max_moves {
+            let Some(tray) = game.generate_blocks(TRAY_SIZE) else {
+                break;
+            };
+
+            for block in tray.iter().take(max_moves - moves) {
+                let Some((rotated, row, column)) = game.best_move(block) else {
+                    return GameSummary {
+                        score: game.score,
+                        moves,
+                        game_over: true,
+                    };
+                };
+
+                game.maybe_place_block(&rotated, row, column)
+                    .expect("best move should always be placeable");
+                moves += 1;
+            }
+        }
+
+        GameSummary {
+            score: game.score,
+            moves,
+            game_over: moves < max_moves,
+        }
     }
 
     fn update_score(&mut self, lines_cleared: usize) -> &mut Self {
-        self.score += lines_cleared * POINTS_PER_LINE_CLEAR;
+        if lines_cleared == 0 {
+            self.combo = 0;
+            return self;
+        }
+
+        self.combo += 1;
+        self.score += self.scoring.points(lines_cleared, self.combo);
         self
     }
 }
 
-impl Default for Game {
-    fn default() -> Self {
-        Self {
-            canvas: Canvas::default(),
-            score: 0,
+/// Orders candidate moves in [`Game::best_move`], best first: most lines cleared, then fewest
+/// cells left occupied, then lowest row, then lowest column.
+type MoveRank = (Reverse<usize>, usize, i32, i32);
+
+/// A point in the search of [`can_place_all`]: which cells are filled, and the shapes of the
+/// blocks still to place, sorted so the same blocks in any order give the same state.
+type TrayState = (Vec<u64>, Vec<Vec<Point>>);
+
+/// Returns true if every block in `blocks` can be placed on `canvas` in some order, and rotation
+/// if `rotation_allowed`, clearing completed lines after each placement.
+fn can_place_all(canvas: &Canvas, blocks: &[Block], rotation_allowed: bool) -> bool {
+    // Only the shape of a block matters to the search, and with rotation only up to turning.
+    let shapes: Vec<Vec<Point>> = blocks
+        .iter()
+        .map(|block| {
+            if rotation_allowed {
+                block.canonical()
+            } else {
+                oriented(block)
+            }
+        })
+        .collect();
+    let remaining: Vec<usize> = (0..blocks.len()).collect();
+    can_place_remaining(
+        canvas,
+        blocks,
+        &shapes,
+        &remaining,
+        rotation_allowed,
+        &mut HashSet::new(),
+    )
+}
+
+/// [`can_place_all`] for the `blocks` whose indices are in `remaining`. Different orders of
+/// placement often reach the same board with the same blocks left, so every such state found to
+/// fail is kept in `failed` and not searched again.
+fn can_place_remaining(
+    canvas: &Canvas,
+    blocks: &[Block],
+    shapes: &[Vec<Point>],
+    remaining: &[usize],
+    rotation_allowed: bool,
+    failed: &mut HashSet<TrayState>,
+) -> bool {
+    if remaining.is_empty() {
+        return true;
+    }
+
+    let mut left: Vec<Vec<Point>> = remaining.iter().map(|&i| shapes[i].clone()).collect();
+    left.sort();
+    let state = (canvas.occupancy_key().to_vec(), left);
+    if failed.contains(&state) {
+        return false;
+    }
+
+    for (i, &index) in remaining.iter().enumerate() {
+        let block = &blocks[index];
+        // Blocks of the same shape are interchangeable, so only try the first of them.
+        if remaining[..i]
+            .iter()
+            .any(|&seen| shapes[seen] == shapes[index])
+        {
+            continue;
+        }
+
+        let mut rest = remaining.to_vec();
+        rest.remove(i);
+        let orientations = if rotation_allowed {
+            block.distinct_rotations()
+        } else {
+            vec![block.clone()]
+        };
+        for rotation in orientations {
+            for (row, column) in canvas.find_placements(&rotation) {
+                let mut next = canvas.clone();
+                next.place(&rotation, row, column)
+                    .expect("found placements always fit");
+                if can_place_remaining(&next, blocks, shapes, &rest, rotation_allowed, failed) {
+                    return true;
+                }
+            }
         }
     }
+
+    failed.insert(state);
+    false
+}
+
+/// Shuffle `blocks` so that blocks with a larger `weight` tend to come first. A block is first
+/// with probability proportional to its weight, scaled by [`Block::standard_set_weight`].
+fn weighted_shuffle<R: Rng + ?Sized>(
+    blocks: &mut Vec<Block>,
+    weight: impl Fn(&Block) -> f32,
+    rng: &mut R,
+) {
+    // Weighted random sampling without replacement: sort by `u^(1/w)` for uniform `u`.
+    let mut keyed: Vec<(f32, Block)> = blocks
+        .drain(..)
+        .map(|block| {
+            let weight = weight(&block) * block.standard_set_weight();
+            (rng.random::<f32>().powf(1.0 / weight), block)
+        })
+        .collect();
+    keyed.sort_by(|a, b| b.0.total_cmp(&a.0));
+
+    blocks.extend(keyed.into_iter().map(|(_, block)| block));
 }
 
 impl Display for Game {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        self.canvas.fmt(f)
+        Display::fmt(&self.canvas, f)
     }
 }
 
 impl Debug for Game {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        self.canvas.fmt(f)
+        Debug::fmt(&self.canvas, f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::canvas::PointStatus;
+
+    #[test]
+    fn best_move_prefers_clearing_a_line() {
+        let mut game = Game::default();
+        let playable = game
+            .canvas
+            .try_make_playable(&Block::line(5), 0, 0)
+            .unwrap();
+        game.canvas.add(&playable);
+
+        let (_, row, column) = game.best_move(&Block::line(3)).unwrap();
+        assert_eq!((0, 5), (row, column));
+    }
+
+    #[test]
+    fn play_random_is_reproducible() {
+        let first = Game::play_random(42, 30);
+        let second = Game::play_random(42, 30);
+
+        assert_eq!(first, second);
+        assert!(first.moves <= 30);
+        assert!(first.game_over || first.moves == 30);
+    }
+
+    #[test]
+    fn assisted_tray_completes_a_line() {
+        let mut game = Game {
+            assist_threshold: Some(0.1),
+            ..Game::with_seed(466)
+        };
+        let playable = game
+            .canvas
+            .try_make_playable(&Block::line(4), 0, 0)
+            .unwrap();
+        game.canvas.add(&playable);
+        let playable = game
+            .canvas
+            .try_make_playable(&Block::line(3), 0, 4)
+            .unwrap();
+        game.canvas.add(&playable);
+
+        let tray = game.generate_blocks(TRAY_SIZE).unwrap();
+        assert_eq!(3, tray.len());
+        assert!(
+            tray.iter().any(|block| {
+                Game::best_move_on(&game.canvas, block, 4).is_some_and(|(cleared, ..)| cleared > 0)
+            }),
+            "Expected a block completing row 0 in {tray:?}"
+        );
+    }
+
+    #[test]
+    fn solvable_trays_are_assisted_too() {
+        let mut game = Game {
+            assist_threshold: Some(0.1),
+            ..Game::with_seed(571)
+        };
+        game.maybe_place_block(&Block::line(4), 0, 0).unwrap();
+        game.maybe_place_block(&Block::line(3), 0, 4).unwrap();
+
+        for _ in 0..10 {
+            let tray = game.generate_solvable_tray(TRAY_SIZE).unwrap();
+            assert_eq!(3, tray.len());
+            assert!(
+                tray.iter().any(|block| {
+                    Game::best_move_on(&game.canvas, block, 4)
+                        .is_some_and(|(cleared, ..)| cleared > 0)
+                }),
+                "Expected a block completing row 0 in {tray:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn placing_awards_points_per_cell() {
+        let mut game = Game {
+            points_per_cell: 1,
+            ..Default::default()
+        };
+
+        game.maybe_place_block(&Block::tee(), 0, 0).unwrap();
+        assert_eq!(4, game.score);
+    }
+
+    #[test]
+    fn should_reroll_when_every_placement_buries_a_cell() {
+        let mut game = Game::default();
+        for column in [1, 3, 5, 7] {
+            let playable = game
+                .canvas
+                .try_make_playable(&Block::rectangle(1, 1), 0, column)
+                .unwrap();
+            game.canvas.add(&playable);
+        }
+
+        // every square straddles a short and a tall column
+        assert!(game.should_reroll(&[Block::rectangle(2, 2), Block::rectangle(3, 3)]));
+        assert!(!game.should_reroll(&[Block::rectangle(2, 2), Block::rectangle(1, 1)]));
+
+        game.reset();
+        assert!(!game.should_reroll(&[Block::rectangle(2, 2), Block::rectangle(3, 3)]));
+    }
+
+    #[test]
+    fn board_mut_keeps_statistics_consistent() {
+        let mut game = Game::with_seed(477);
+        game.maybe_place_block(&Block::line(5), 0, 0).unwrap();
+        game.maybe_place_block(&Block::line(3), 0, 5).unwrap();
+        game.maybe_place_block(&Block::tee(), 0, 0).unwrap();
+        let (score, combo) = (game.score, game.combo());
+        let moves = game.moves().to_vec();
+
+        // reading through the guard is not an edit
+        assert_eq!(game.board().generation(), game.board_mut().generation());
+        assert!(game.is_replayable());
+
+        let playable = game.board().try_make_playable(&Block::tee(), 2, 2).unwrap();
+        game.board_mut().add(&playable);
+
+        assert!(!game.board().can_fit_at(&Block::rectangle(1, 1), 3, 3));
+        assert_eq!(score, game.score);
+        assert_eq!(combo, game.combo());
+        assert_eq!(moves, game.moves());
+        assert!(!game.is_replayable());
+        let replayed = Game::with_seed(477).replay(game.moves()).unwrap();
+        assert_ne!(game.board(), replayed.board());
+
+        game.reset();
+        assert!(game.is_replayable());
+    }
+
+    #[test]
+    fn estimates_moves_remaining() {
+        let tray = [Block::rectangle(2, 2), Block::tee(), Block::line(3)];
+
+        let game = Game::default();
+        assert!(game.estimate_moves_remaining(&tray) >= 10);
+        assert_eq!(0, game.estimate_moves_remaining(&[]));
+
+        let mut game = Game::default();
+        for row in 0..7 {
+            for column in [0, 3, 6] {
+                let playable = game
+                    .canvas
+                    .try_make_playable(&Block::line(2), row, column)
+                    .unwrap();
+                game.canvas.add(&playable);
+            }
+        }
+        assert!(game.estimate_moves_remaining(&tray) <= 2);
+    }
+
+    #[test]
+    fn clearing_often_makes_the_game_harder() {
+        let mut game = Game {
+            adaptive_difficulty: Some(AdaptiveDifficulty::new(0.2)),
+            ..Default::default()
+        };
+
+        for _ in 0..10 {
+            game.maybe_place_block(&Block::line(5), 0, 0).unwrap();
+            game.maybe_place_block(&Block::line(3), 0, 5).unwrap();
+        }
+
+        assert!(game.difficulty().unwrap() > 0.5);
+    }
+
+    #[test]
+    fn clearing_rarely_makes_the_game_easier() {
+        let mut game = Game {
+            adaptive_difficulty: Some(AdaptiveDifficulty::new(0.2)),
+            ..Default::default()
+        };
+
+        for row in 0..8 {
+            for column in 0..2 {
+                game.maybe_place_block(&Block::rectangle(1, 1), row, column)
+                    .unwrap();
+            }
+        }
+
+        assert!(game.difficulty().unwrap() < 0.5);
+        assert_eq!(None, Game::default().difficulty());
+    }
+
+    #[test]
+    fn placing_awkward_blocks_awards_their_weight() {
+        let mut plain = Game::default();
+        let mut weighted = Game {
+            award_placement_weight: true,
+            ..Default::default()
+        };
+
+        plain.maybe_place_block(&Block::tee(), 0, 0).unwrap();
+        weighted.maybe_place_block(&Block::tee(), 0, 0).unwrap();
+        assert_eq!(0, plain.score);
+        assert_eq!(Block::tee().placement_weight(), weighted.score);
+    }
+
+    #[test]
+    fn auto_play_places_pieces_greedily() {
+        let mut game = Game::default();
+        let pieces = [Block::line(5), Block::line(3), Block::rectangle(1, 1)];
+
+        assert_eq!(
+            vec![
+                MoveOutcome {
+                    row: 0,
+                    column: 0,
+                    lines_cleared: 0,
+                    points: 0,
+                },
+                MoveOutcome {
+                    row: 0,
+                    column: 5,
+                    lines_cleared: 1,
+                    points: POINTS_PER_LINE_CLEAR + PERFECT_CLEAR_BONUS,
+                },
+                MoveOutcome {
+                    row: 0,
+                    column: 0,
+                    lines_cleared: 0,
+                    points: 0,
+                },
+            ],
+            game.auto_play(&pieces)
+        );
+        assert_eq!(POINTS_PER_LINE_CLEAR + PERFECT_CLEAR_BONUS, game.score);
+    }
+
+    #[test]
+    fn auto_play_stops_at_unplaceable_piece() {
+        let mut game = Game::default();
+        for row in 0..7 {
+            for column in [0, 3, 6] {
+                game.maybe_place_block(&Block::line(2), row, column)
+                    .unwrap();
+            }
+        }
+
+        let pieces = [
+            Block::rectangle(1, 1),
+            Block::rectangle(3, 3),
+            Block::rectangle(1, 1),
+        ];
+        assert_eq!(1, game.auto_play(&pieces).len());
+    }
+
+    #[test]
+    fn naive_generation_fills_the_last_gap() {
+        let mut canvas = Canvas::new(3, 3);
+        let bottom = canvas
+            .try_make_playable(&Block::rectangle(3, 2), 0, 0)
+            .unwrap();
+        canvas.add(&bottom);
+        let top = canvas.try_make_playable(&Block::line(2), 2, 0).unwrap();
+        canvas.add(&top);
+
+        let block = Game::default()
+            .naive_generate_block(&mut canvas)
+            .expect("a single cell is left open");
+
+        assert_eq!(1, block.cell_count());
+        assert!(canvas.can_fit(&Block::rectangle(1, 1)).is_none());
+    }
+
+    /// A default sized board whose bottom `count` rows are each missing only their last cell.
+    fn almost_complete_rows(count: usize) -> Canvas {
+        let mut canvas = Canvas::default();
+        for row in 0..count as i32 {
+            for column in 0..canvas.columns as i32 - 1 {
+                canvas.set(row, column, PointStatus::Occupied(0));
+            }
+        }
+        // keep a stray cell in the top left so clears never empty the board
+        canvas.set(canvas.rows as i32 - 1, 0, PointStatus::Occupied(0));
+        canvas
+    }
+
+    #[test]
+    fn consecutive_clears_build_a_combo() {
+        let mut game = Game::default();
+        *game.board_mut() = almost_complete_rows(2);
+        let dot = Block::rectangle(1, 1);
+
+        game.maybe_place_block(&dot, 0, 7).unwrap();
+        assert_eq!(1, game.combo());
+        assert_eq!(POINTS_PER_LINE_CLEAR, game.score);
+
+        game.maybe_place_block(&dot, 1, 7).unwrap();
+        assert_eq!(2, game.combo());
+        assert_eq!(3 * POINTS_PER_LINE_CLEAR, game.score);
+    }
+
+    #[test]
+    fn placement_without_a_clear_breaks_the_combo() {
+        let mut game = Game::default();
+        *game.board_mut() = almost_complete_rows(2);
+        let dot = Block::rectangle(1, 1);
+
+        game.maybe_place_block(&dot, 0, 7).unwrap();
+        game.maybe_place_block(&dot, 5, 5).unwrap();
+        assert_eq!(0, game.combo());
+
+        game.maybe_place_block(&dot, 1, 7).unwrap();
+        assert_eq!(1, game.combo());
+        assert_eq!(2 * POINTS_PER_LINE_CLEAR, game.score);
+    }
+
+    #[test]
+    fn clearing_several_lines_at_once_earns_a_bonus() {
+        for (lines, block, expected) in [
+            (1, Block::rectangle(1, 1), 50),
+            (2, Block::line_vertical(2), 125),
+            (3, Block::line_vertical(3), 200),
+        ] {
+            let mut game = Game::default();
+            *game.board_mut() = almost_complete_rows(lines);
+
+            game.maybe_place_block(&block, 0, 7).unwrap();
+            assert_eq!(
+                lines * POINTS_PER_LINE_CLEAR + (lines - 1) * MULTI_LINE_BONUS,
+                game.score
+            );
+            assert_eq!(expected, game.score);
+        }
+    }
+
+    #[test]
+    fn game_continues_while_any_block_fits() {
+        let mut game = Game::default();
+        *game.board_mut() = Canvas::from_str_grid(
+            "
+            # # # # # # # #
+            # # # # # # # #
+            # # # # # # # #
+            # # # # . # # #
+            # # # # # # # #
+            # # # # # # # #
+            # # # # # # # #
+            # # # # # # # #
+            ",
+        )
+        .unwrap();
+
+        assert!(!game.is_game_over(&[Block::rectangle(2, 2), Block::rectangle(1, 1)]));
+        assert!(game.is_game_over(&[Block::rectangle(2, 2), Block::line(2)]));
+    }
+
+    #[test]
+    fn game_is_over_when_no_gap_is_big_enough() {
+        let mut game = Game::default();
+        *game.board_mut() = Canvas::from_str_grid(
+            "
+            . # . # . # . #
+            # . # . # . # .
+            . # . # . # . #
+            # . # . # . # .
+            . # . # . # . #
+            # . # . # . # .
+            . # . # . # . #
+            # . # . # . # .
+            ",
+        )
+        .unwrap();
+
+        assert!(game.is_game_over(&[Block::rectangle(2, 2)]));
+        assert!(!game.is_game_over(&[Block::rectangle(2, 2), Block::diagonal(3)]));
+    }
+
+    #[test]
+    fn seeded_games_generate_the_same_blocks() {
+        let coordinates = |tray: Vec<Block>| -> Vec<Vec<Point>> {
+            tray.iter().map(|b| b.coordinates().clone()).collect()
+        };
+
+        let first = Game::with_seed(7);
+        let second = Game::with_seed(7);
+        for _ in 0..5 {
+            assert_eq!(
+                coordinates(first.generate_blocks(3).unwrap()),
+                coordinates(second.generate_blocks(3).unwrap())
+            );
+        }
+
+        let mut first_canvas = Canvas::default();
+        let mut second_canvas = Canvas::default();
+        let first_block = first.naive_generate_block(&mut first_canvas).unwrap();
+        let second_block = second.naive_generate_block(&mut second_canvas).unwrap();
+        assert_eq!(first_block.coordinates(), second_block.coordinates());
+        assert_eq!(first_canvas, second_canvas);
+    }
+
+    #[test]
+    fn tray_refills_only_once_emptied() {
+        let mut game = Game::with_seed(3);
+        assert_eq!(3, game.tray().len());
+        let remaining = game.tray()[1..].to_vec();
+
+        let taken = game.take_from_tray(0).unwrap();
+        assert_eq!(2, game.tray().len());
+        assert!(game.take_from_tray(2).is_none());
+        let (_, row, column) = game.best_move(&taken).unwrap();
+        game.maybe_place_block(&taken, row, column).unwrap();
+
+        game.take_from_tray(1).unwrap();
+        assert_eq!(1, game.tray().len());
+        assert_eq!(remaining[0].coordinates(), game.tray()[0].coordinates());
+
+        game.take_from_tray(0).unwrap();
+        assert_eq!(3, game.tray().len());
+    }
+
+    #[test]
+    fn best_placement_clears_a_line() {
+        let mut game = Game::default();
+        *game.board_mut() = Canvas::from_str_grid(
+            "
+            . . . . . . . .
+            . . . . . . . .
+            . . . . . . . .
+            . . . . . . . .
+            . . . . . . . .
+            . . . . . . . .
+            # # # # # # . .
+            # # # # # # . .
+            ",
+        )
+        .unwrap();
+
+        // Lying flat in either gap completes a row, standing upright in it completes nothing.
+        let (rotated, row, column) = game.best_placement(&Block::line(2)).unwrap();
+        assert_eq!(6, column);
+        assert!(rotated.coordinates().iter().all(|p| p.y == 0));
+
+        game.maybe_place_block(&rotated, row, column).unwrap();
+        assert_eq!(POINTS_PER_LINE_CLEAR, game.score);
+    }
+
+    #[test]
+    fn best_placement_breaks_ties_by_occupancy() {
+        let mut game = Game::default();
+        *game.board_mut() = Canvas::from_str_grid(
+            "
+            . . . . .
+            # . . . .
+            # # # # .
+            ",
+        )
+        .unwrap();
+
+        // Completing the column is found first, but completing the longer row leaves fewer
+        // cells behind.
+        let (_, row, column) = game.best_placement(&Block::rectangle(1, 1)).unwrap();
+        assert_eq!((0, 4), (row, column));
+    }
+
+    #[test]
+    fn best_placement_breaks_remaining_ties_by_row_then_column() {
+        let mut game = Game::default();
+        *game.board_mut() = Canvas::from_str_grid(
+            "
+            . . .
+            . . .
+            # . .
+            ",
+        )
+        .unwrap();
+
+        // every placement clears nothing and leaves two cells
+        let (_, row, column) = game.best_placement(&Block::rectangle(1, 1)).unwrap();
+        assert_eq!((0, 1), (row, column));
+        assert_eq!(
+            game.best_move(&Block::rectangle(1, 1)),
+            game.best_placement(&Block::rectangle(1, 1))
+        );
+    }
+
+    #[test]
+    fn best_placement_needs_room() {
+        let mut game = Game::default();
+        *game.board_mut() = Canvas::from_str_grid("# #\n# .").unwrap();
+
+        assert!(game.best_placement(&Block::line(2)).is_none());
+        assert!(game.best_placement(&Block::rectangle(1, 1)).is_some());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn save_and_load_round_trip() {
+        let mut game = Game {
+            points_per_cell: 2,
+            adaptive_difficulty: Some(AdaptiveDifficulty::new(0.5)),
+            ..Game::with_seed(11)
+        };
+        *game.board_mut() = almost_complete_rows(1);
+        game.maybe_place_block(&Block::rectangle(1, 1), 0, 7)
+            .unwrap();
+        game.maybe_place_block(&Block::tee(), 3, 3).unwrap();
+        game.take_from_tray(0).unwrap();
+
+        let loaded = Game::load(&game.save()).unwrap();
+        assert_eq!(game.score, loaded.score);
+        assert_eq!(game.combo(), loaded.combo());
+        assert_eq!(game.board(), loaded.board());
+        assert_eq!(game.points_per_cell, loaded.points_per_cell);
+        assert_eq!(game.difficulty(), loaded.difficulty());
+        let coordinates = |tray: &[Block]| -> Vec<Vec<Point>> {
+            tray.iter().map(|b| b.coordinates().clone()).collect()
+        };
+        assert_eq!(coordinates(&game.tray), coordinates(&loaded.tray));
+
+        // the seeded generator picks up where it left off
+        assert_eq!(
+            coordinates(&game.new_tray().unwrap()),
+            coordinates(&loaded.new_tray().unwrap())
+        );
+
+        assert!(Game::load("{").is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn loads_saves_missing_newer_fields() {
+        let mut game = Game::with_seed(572);
+        game.maybe_place_block(&Block::tee(), 0, 0).unwrap();
+        let mut save: serde_json::Value = serde_json::from_str(&game.save()).unwrap();
+        let fields = save.as_object_mut().unwrap();
+        for newer in ["rotation_allowed", "moves", "held", "seeded_rng"] {
+            fields.remove(newer).unwrap();
+        }
+
+        let loaded = Game::load(&save.to_string()).unwrap();
+        assert!(loaded.rotation_allowed);
+        assert!(loaded.moves().is_empty());
+        assert_eq!(game.board(), loaded.board());
+        assert_eq!(game.score, loaded.score);
+    }
+
+    #[test]
+    fn custom_scoring_is_applied() {
+        let mut game = Game {
+            scoring: ScoreConfig {
+                per_line: 2 * POINTS_PER_LINE_CLEAR,
+                combo_step: 1,
+                multi_clear_bonus: 2 * MULTI_LINE_BONUS,
+            },
+            ..Default::default()
+        };
+        *game.board_mut() = almost_complete_rows(3);
+
+        game.maybe_place_block(&Block::line_vertical(2), 0, 7)
+            .unwrap();
+        assert_eq!(2 * 125, game.score);
+
+        game.maybe_place_block(&Block::rectangle(1, 1), 2, 7)
+            .unwrap();
+        assert_eq!(2 * 125 + 2 * 2 * 50, game.score);
+    }
+
+    #[test]
+    fn combo_step_controls_the_multiplier() {
+        let flat = ScoreConfig {
+            combo_step: 0,
+            ..Default::default()
+        };
+        assert_eq!(50, flat.points(1, 1));
+        assert_eq!(50, flat.points(1, 4));
+
+        let steep = ScoreConfig {
+            combo_step: 2,
+            ..Default::default()
+        };
+        assert_eq!(50, steep.points(1, 1));
+        assert_eq!(250, steep.points(1, 3));
+        assert_eq!(0, steep.points(0, 3));
+    }
+
+    #[test]
+    fn perfect_clear_awards_bonus_once() {
+        let mut game = Game::default();
+        game.maybe_place_block(&Block::line(5), 0, 0).unwrap();
+
+        game.maybe_place_block(&Block::line(3), 0, 5).unwrap();
+        assert!(game.board().is_empty());
+        assert_eq!(POINTS_PER_LINE_CLEAR + PERFECT_CLEAR_BONUS, game.score);
+
+        game.maybe_place_block(&Block::rectangle(1, 1), 4, 4)
+            .unwrap();
+        assert_eq!(POINTS_PER_LINE_CLEAR + PERFECT_CLEAR_BONUS, game.score);
+    }
+
+    #[test]
+    fn partial_clear_gets_no_perfect_clear_bonus() {
+        let mut game = Game::default();
+        *game.board_mut() = almost_complete_rows(1);
+
+        game.maybe_place_block(&Block::rectangle(1, 1), 0, 7)
+            .unwrap();
+        assert!(!game.board().is_empty());
+        assert_eq!(POINTS_PER_LINE_CLEAR, game.score);
+    }
+
+    #[test]
+    fn holding_into_an_empty_slot_returns_nothing() {
+        let mut game = Game::default();
+
+        assert_eq!(None, game.hold(Block::tee()));
+        assert_eq!(Some(&Block::tee()), game.held());
+    }
+
+    #[test]
+    fn holding_swaps_with_the_held_block() {
+        let mut game = Game::default();
+        game.hold(Block::tee());
+
+        assert_eq!(Some(Block::tee()), game.hold(Block::line(3)));
+        assert_eq!(Some(&Block::line(3)), game.held());
+
+        game.reset();
+        assert_eq!(None, game.held());
+    }
+
+    #[test]
+    fn difficulty_tiers_bias_generation() {
+        const DRAWS: usize = 4000;
+        let small = |block: &Block| {
+            matches!(block.variant(), Variant::Rectangle | Variant::Line) && block.cell_count() <= 4
+        };
+        let awkward = |block: &Block| block.placement_weight() >= 8;
+        // the first block tried is drawn with probability proportional to its weight
+        let share = |tier: Difficulty, class: &dyn Fn(&Block) -> bool| {
+            let blocks = Block::standard_set();
+            let weight = |block: &Block| tier.weight(block) * block.standard_set_weight();
+            let total: f32 = blocks.iter().map(weight).sum();
+            let matching: f32 = blocks.iter().filter(|block| class(block)).map(weight).sum();
+            matching / total
+        };
+
+        assert!(share(Difficulty::Easy, &small) > share(Difficulty::Normal, &small));
+        assert!(share(Difficulty::Hard, &awkward) > share(Difficulty::Normal, &awkward));
+
+        for tier in [Difficulty::Easy, Difficulty::Normal, Difficulty::Hard] {
+            let game = Game {
+                difficulty_tier: tier,
+                ..Game::with_seed(7)
+            };
+            let draws: Vec<Block> = (0..DRAWS)
+                .map(|_| game.naive_generate_block(&mut Canvas::default()).unwrap())
+                .collect();
+
+            for class in [small, awkward] {
+                let observed =
+                    draws.iter().filter(|block| class(block)).count() as f32 / DRAWS as f32;
+                let expected = share(tier, &class);
+                assert!(
+                    (observed - expected).abs() < 0.03,
+                    "{tier:?}: expected {expected}, observed {observed}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn replaying_the_move_log_restores_the_game() {
+        let mut game = Game::with_seed(553);
+        // a block whose coordinates are not flush with its origin
+        let mut shifted = Block::tee();
+        shifted.rotate_right().translate(2, 1);
+        game.maybe_place_block(&shifted, 1, 0).unwrap();
+        for _ in 0..6 {
+            let Some(tray) = game.generate_blocks(TRAY_SIZE) else {
+                break;
+            };
+            game.auto_play(&tray);
+        }
+        assert!(game.moves().len() > 5);
+        assert!(game.score > 0);
+
+        let replayed = Game::with_seed(553).replay(game.moves()).unwrap();
+        assert_eq!(game.board(), replayed.board());
+        assert_eq!(game.score, replayed.score);
+        assert_eq!(game.moves(), replayed.moves());
+    }
+
+    #[test]
+    fn replay_uses_the_settings_of_the_game_it_starts_from() {
+        let configured = || Game {
+            canvas: Canvas::new(6, 10),
+            points_per_cell: 2,
+            award_placement_weight: true,
+            ..Game::with_seed(553)
+        };
+        let mut game = configured();
+        game.maybe_place_block(&Block::rectangle(2, 2), 0, 8)
+            .unwrap();
+        game.maybe_place_block(&Block::line_horizontal(4), 5, 0)
+            .unwrap();
+
+        let replayed = configured().replay(game.moves()).unwrap();
+        assert_eq!(game.board(), replayed.board());
+        assert_eq!(game.score, replayed.score);
+
+        // the moves leave the default 8x8 board
+        assert_eq!(
+            Err(PlaceError::OutOfBounds),
+            Game::with_seed(553)
+                .replay(game.moves())
+                .map(|game| game.score)
+        );
+    }
+
+    #[test]
+    fn recorded_moves_remember_orientation() {
+        let mut game = Game::default();
+        let mut elle = Block::elle(3, 2);
+        elle.rotate_left();
+        let (row, column) = game.board().find_placements(&elle)[0];
+        game.maybe_place_block(&elle, row, column).unwrap();
+
+        let recorded = &game.moves()[0];
+        assert_eq!(elle.canonical(), recorded.shape);
+        assert_eq!(oriented(&elle), oriented(&recorded.block()));
+        assert_eq!(elle.color(), recorded.block().color());
+
+        game.reset();
+        assert!(game.moves().is_empty());
+    }
+
+    #[test]
+    fn failed_placements_report_the_reason() {
+        let mut game = Game::default();
+        game.maybe_place_block(&Block::rectangle(2, 2), 0, 0)
+            .unwrap();
+
+        assert_eq!(
+            Err(PlaceError::Overlap),
+            game.maybe_place_block(&Block::tee(), 1, 1)
+        );
+        assert_eq!(
+            Err(PlaceError::OutOfBounds),
+            game.maybe_place_block(&Block::line(5), 0, 4)
+        );
+        assert_eq!(4, game.board().count_occupied());
+        assert_eq!(1, game.moves().len());
+    }
+
+    /// Place every block of `tray` on `canvas` in some order and rotation, by brute force.
+    fn place_all(canvas: &Canvas, tray: &[Block]) -> bool {
+        tray.is_empty()
+            || (0..tray.len()).any(|i| {
+                let mut rest = tray.to_vec();
+                let mut block = rest.remove(i);
+                (0..4).any(|_| {
+                    block.rotate_right();
+                    canvas
+                        .find_placements(&block)
+                        .into_iter()
+                        .any(|(row, column)| {
+                            let mut next = canvas.clone();
+                            next.place(&block, row, column).unwrap();
+                            place_all(&next, &rest)
+                        })
+                })
+            })
+    }
+
+    #[test]
+    fn solvable_trays_can_be_fully_placed() {
+        let mut game = Game::with_seed(571);
+        let tray = game.generate_solvable_tray(3).unwrap();
+        assert_eq!(3, tray.len());
+        assert!(place_all(game.board(), &tray));
+
+        // only a 3x3 pocket and the top row are free
+        for row in 0..7 {
+            for column in 0..8 {
+                if column >= 3 || row >= 3 {
+                    game.board_mut().set(row, column, PointStatus::Occupied(0));
+                }
+            }
+        }
+        for _ in 0..20 {
+            let tray = game.generate_solvable_tray(3).unwrap();
+            assert_eq!(3, tray.len());
+            assert!(place_all(game.board(), &tray), "{tray:?}");
+        }
+
+        game.board_mut().resize(2, 2);
+        for (row, column) in [(0, 0), (0, 1), (1, 0), (1, 1)] {
+            game.board_mut().set(row, column, PointStatus::Occupied(0));
+        }
+        assert_eq!(None, game.generate_solvable_tray(3));
+    }
+
+    #[test]
+    fn generation_respects_rotation_being_disabled() {
+        // only the leftmost column is free
+        let mut board = Canvas::default();
+        for column in 1..8 {
+            for row in 0..8 {
+                board.set(row, column, PointStatus::Occupied(0));
+            }
+        }
+
+        let mut fixed = Game {
+            rotation_allowed: false,
+            ..Game::with_seed(572)
+        };
+        *fixed.board_mut() = board.clone();
+        let mut free = Game::with_seed(572);
+        *free.board_mut() = board;
+
+        let mut rotated_any = false;
+        for _ in 0..10 {
+            for block in fixed.generate_blocks(3).unwrap() {
+                assert_eq!(Block::rectangle(1, 1), block);
+            }
+            for block in fixed.generate_solvable_tray(3).unwrap() {
+                assert_eq!(Block::rectangle(1, 1), block);
+            }
+            for block in free.generate_blocks(1).unwrap() {
+                assert_eq!(1, block.dimensions().width);
+                rotated_any |= block.dimensions().height > 1;
+            }
+        }
+        assert!(rotated_any);
+
+        let line = Block::line(3);
+        assert!(fixed.is_game_over(std::slice::from_ref(&line)));
+        assert_eq!(None, fixed.best_move(&line));
+        assert!(!free.is_game_over(std::slice::from_ref(&line)));
+        assert!(free.best_move(&line).is_some());
+    }
+
+    #[test]
+    fn reset_restarts_adaptive_difficulty() {
+        let mut game = Game {
+            adaptive_difficulty: Some(AdaptiveDifficulty::new(0.0)),
+            ..Default::default()
+        };
+        *game.board_mut() = almost_complete_rows(1);
+        game.maybe_place_block(&Block::rectangle(1, 1), 0, 7)
+            .unwrap();
+        assert!(game.difficulty().unwrap() > 0.5);
+
+        game.reset();
+        assert_eq!(Some(0.5), game.difficulty());
+    }
+
+    #[test]
+    fn games_can_be_shared_between_threads() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<Game>();
     }
 }
@@ -6,3 +6,11 @@ pub mod canvas;
 
 /// Playable blocks.
 pub mod block;
+
+/// Convenient re-exports of the core types.
+pub mod prelude;
+
+/// JavaScript bindings for running a game in the browser, built with
+/// `cargo build --target wasm32-unknown-unknown --features wasm`.
+#[cfg(feature = "wasm")]
+pub mod wasm;
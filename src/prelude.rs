@@ -0,0 +1,20 @@
+//! Everything needed to build a game with a single import.
+//!
+//! ```
+//! use jcblocks::prelude::*;
+//!
+//! let mut game = Game::default();
+//! let block = Block::tee();
+//! assert_eq!("Tee", Variant::Tee.to_string());
+//!
+//! game.maybe_place_block(&block, 0, 0).unwrap();
+//! assert!(!game.board().can_fit_at(&Block::rectangle(1, 1), 0, 0));
+//!
+//! let blank: Canvas = Canvas::default();
+//! assert_eq!(Some(&PointStatus::Empty), blank.contents().first());
+//! assert_eq!(Point { x: 0, y: 0 }, Point::default());
+//! ```
+
+pub use crate::block::{Block, BlockError, Point, Variant};
+pub use crate::canvas::{Canvas, CellInfo, ParseCanvasError, PlaceError, PointStatus};
+pub use crate::game::Game;
@@ -0,0 +1,202 @@
+use wasm_bindgen::prelude::*;
+
+use crate::{
+    canvas::{Canvas, PointStatus},
+    game::Game,
+};
+
+/// The board as sent to JavaScript: `cells[row][column]` with row 0 at the bottom, holding the
+/// [`crate::canvas::CellInfo::block_id`] of each cell of [`Canvas::snapshot`]. That is `null`
+/// for an empty cell or one marked for removal, and otherwise the id of the block filling it, 0
+/// for cells filled by anything other than a placed block.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+struct BoardJson {
+    rows: usize,
+    columns: usize,
+    cells: Vec<Vec<Option<u8>>>,
+}
+
+impl From<&Canvas> for BoardJson {
+    fn from(canvas: &Canvas) -> Self {
+        let cells = canvas
+            .snapshot()
+            .into_iter()
+            .map(|row| row.into_iter().map(|cell| cell.block_id).collect())
+            .collect();
+
+        BoardJson {
+            rows: canvas.rows,
+            columns: canvas.columns,
+            cells,
+        }
+    }
+}
+
+impl From<&BoardJson> for Canvas {
+    fn from(board: &BoardJson) -> Self {
+        let mut canvas = Canvas::new(board.rows, board.columns);
+        for (row, cells) in board.cells.iter().enumerate() {
+            for (column, cell) in cells.iter().enumerate() {
+                if let Some(id) = cell {
+                    canvas.set(row as i32, column as i32, PointStatus::Occupied(*id));
+                }
+            }
+        }
+
+        canvas
+    }
+}
+
+/// A [`Game`] driven from JavaScript.
+#[wasm_bindgen]
+pub struct WasmGame {
+    game: Game,
+}
+
+#[wasm_bindgen]
+impl WasmGame {
+    /// Start a game on an empty default board.
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> WasmGame {
+        WasmGame {
+            game: Game::default(),
+        }
+    }
+
+    /// Start a game whose blocks are reproducible, see [`Game::with_seed`]. The seed is a `u32`
+    /// so that it arrives from JavaScript as a plain number rather than a `BigInt`.
+    pub fn with_seed(seed: u32) -> WasmGame {
+        WasmGame {
+            game: Game::with_seed(seed.into()),
+        }
+    }
+
+    pub fn score(&self) -> usize {
+        self.game.score
+    }
+
+    /// Place the tray block at `index`, turned right `rotation` times, with its origin at
+    /// `row`/`column`. Returns false, leaving the tray as it was, if the block does not fit or
+    /// is turned while [`Game::rotation_allowed`] is unset.
+    pub fn place(&mut self, index: usize, rotation: i32, row: i32, column: i32) -> bool {
+        let Some(mut block) = self.game.tray().get(index).cloned() else {
+            return false;
+        };
+        if !self.game.rotation_allowed && rotation.rem_euclid(4) != 0 {
+            return false;
+        }
+
+        block.rotate_n(rotation);
+        if self.game.maybe_place_block(&block, row, column).is_err() {
+            return false;
+        }
+
+        self.game.take_from_tray(index);
+        true
+    }
+
+    /// The blocks left to place as JSON, each a list of `{"x": .., "y": ..}` cells.
+    pub fn tray(&mut self) -> String {
+        let tray: Vec<_> = self
+            .game
+            .tray()
+            .iter()
+            .map(|block| block.coordinates().clone())
+            .collect();
+        serde_json::to_string(&tray).expect("coordinates are always serializable")
+    }
+
+    /// The board as JSON, `{"rows": .., "columns": .., "cells": [[..], ..]}` where `cells[row]
+    /// [column]` is the id of the block filling the cell, or `null` if it is empty or marked for
+    /// removal. Row 0 is the bottom row.
+    pub fn board(&self) -> String {
+        serde_json::to_string(&BoardJson::from(self.game.board()))
+            .expect("board is always serializable")
+    }
+
+    /// Returns true if no block left in the tray fits anywhere on the board.
+    pub fn is_game_over(&mut self) -> bool {
+        let tray = self.game.tray().to_vec();
+        self.game.is_game_over(&tray)
+    }
+}
+
+impl Default for WasmGame {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::block::Block;
+
+    #[test]
+    fn board_json_round_trips() {
+        let mut canvas = Canvas::new(2, 3);
+        let tee = Block::tee();
+        canvas.add(&canvas.try_make_playable(&tee, 0, 0).unwrap());
+
+        let json = serde_json::to_string(&BoardJson::from(&canvas)).unwrap();
+        assert_eq!(
+            format!(
+                r#"{{"rows":2,"columns":3,"cells":[[{id},{id},{id}],[null,{id},null]]}}"#,
+                id = tee.color()
+            ),
+            json
+        );
+
+        let parsed: BoardJson = serde_json::from_str(&json).unwrap();
+        assert_eq!(canvas, Canvas::from(&parsed));
+    }
+
+    #[test]
+    fn board_json_matches_the_snapshot() {
+        let mut canvas = Canvas::from_str_grid("# . .\n. . #\n# # #").unwrap();
+        canvas.mark_completed_lines();
+
+        let board = BoardJson::from(&canvas);
+        assert_eq!(
+            vec![
+                vec![None, None, None],
+                vec![None, None, Some(0)],
+                vec![Some(0), None, None],
+            ],
+            board.cells
+        );
+        for (json_row, snapshot_row) in board.cells.iter().zip(canvas.snapshot()) {
+            let ids: Vec<_> = snapshot_row.iter().map(|cell| cell.block_id).collect();
+            assert_eq!(&ids, json_row);
+        }
+    }
+
+    #[test]
+    fn can_play_through_the_bindings() {
+        let mut game = WasmGame::with_seed(563);
+        let tray: Vec<Vec<serde_json::Value>> = serde_json::from_str(&game.tray()).unwrap();
+        assert_eq!(3, tray.len());
+
+        let placed = (0..8)
+            .flat_map(|row| (0..8).map(move |column| (row, column)))
+            .any(|(row, column)| game.place(0, 1, row, column));
+        assert!(placed);
+        assert!(!game.place(5, 0, 0, 0));
+        assert!(!game.is_game_over());
+
+        let tray: Vec<Vec<serde_json::Value>> = serde_json::from_str(&game.tray()).unwrap();
+        assert_eq!(2, tray.len());
+        let board: BoardJson = serde_json::from_str(&game.board()).unwrap();
+        assert!(board.cells.iter().flatten().any(Option::is_some));
+        assert_eq!(game.game.score, game.score());
+    }
+
+    #[test]
+    fn rejects_rotation_when_disabled() {
+        let mut game = WasmGame::with_seed(572);
+        game.game.rotation_allowed = false;
+
+        assert!(!game.place(0, 1, 0, 0));
+        assert!(game.place(0, 4, 0, 0));
+    }
+}